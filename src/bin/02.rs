@@ -1,3 +1,5 @@
+use advent_of_code::parse::{self, ParseResult};
+
 advent_of_code::solution!(2);
 
 fn is_safe(sequence: &[u32]) -> bool {
@@ -20,14 +22,11 @@ fn is_safe(sequence: &[u32]) -> bool {
     increasing || decreasing // strictly ordered
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> ParseResult<u32> {
     let mut total_safe = 0;
 
-    for line in input.lines() {
-        let nums: Vec<u32> = line
-            .split_ascii_whitespace()
-            .filter_map(|s| s.parse::<u32>().ok())
-            .collect();
+    for line in parse::lines(input) {
+        let nums: Vec<u32> = line.words()?;
 
         if nums.len() < 2 {
             continue;
@@ -38,17 +37,14 @@ pub fn part_one(input: &str) -> Option<u32> {
         }
     }
 
-    Some(total_safe)
+    Ok(total_safe)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> ParseResult<u32> {
     let mut total_safe = 0;
 
-    for line in input.lines() {
-        let nums: Vec<u32> = line
-            .split_ascii_whitespace()
-            .filter_map(|s| s.parse::<u32>().ok())
-            .collect();
+    for line in parse::lines(input) {
+        let nums: Vec<u32> = line.words()?;
 
         if nums.len() < 2 {
             continue;
@@ -70,7 +66,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(total_safe)
+    Ok(total_safe)
 }
 
 #[cfg(test)]
@@ -80,12 +76,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+        assert_eq!(result, Ok(4));
     }
 }
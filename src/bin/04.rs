@@ -1,5 +1,6 @@
 advent_of_code::solution!(4);
 
+use advent_of_code::geometry::{Vec2, NEIGHBORS_8};
 use ndarray::Array2;
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -12,45 +13,25 @@ pub fn part_one(input: &str) -> Option<u32> {
     let grid = Array2::from_shape_vec((side_len, side_len), flat_grid).unwrap();
 
     let word = "XMAS";
-    let word_len = word.len();
     let (rows, cols) = grid.dim();
     let word_chars: Vec<char> = word.chars().collect();
-    let mut count = 0;
-
-    let directions = [
-        (0, 1),   // e
-        (0, -1),  // w
-        (1, 0),   // s
-        (-1, 0),  // n
-        (1, 1),   // se
-        (1, -1),  // sw
-        (-1, 1),  // ne
-        (-1, -1), // nw
-    ];
 
-    // would have been a filter map; closure borrow errors are dumb
-    for r in 0..rows {
-        for c in 0..cols {
-            for &(dr, dc) in &directions {
-                let mut found = true;
-                for (i, cur_char) in word_chars.iter().enumerate().take(word_len) {
-                    let nr = r as isize + dr * i as isize;
-                    let nc = c as isize + dc * i as isize;
-                    if nr < 0 || nc < 0 || nr >= rows as isize || nc >= cols as isize {
-                        found = false;
-                        break;
-                    }
-                    if grid[[nr as usize, nc as usize]] != *cur_char {
-                        found = false;
-                        break;
-                    }
-                }
-                if found {
-                    count += 1;
-                }
-            }
-        }
-    }
+    let count = grid
+        .indexed_iter()
+        .map(|(idx, _)| Vec2::from_index(idx))
+        .map(|start| {
+            NEIGHBORS_8
+                .iter()
+                .filter(|&&dir| {
+                    word_chars.iter().enumerate().all(|(i, cur_char)| {
+                        (start + dir * i as isize)
+                            .to_index(cols, rows)
+                            .is_some_and(|idx| grid[idx] == *cur_char)
+                    })
+                })
+                .count() as u32
+        })
+        .sum();
 
     Some(count)
 }
@@ -66,22 +47,21 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     // only check the diags; will check each in both directions
     let diagonals = [
-        (-1, -1), // nw -> se
-        (-1, 1),  // ne -> sw
+        Vec2::new(-1, -1), // nw -> se
+        Vec2::new(1, -1),  // ne -> sw
     ];
 
     let count = (1..grid.nrows() - 1)
         .flat_map(|r| (1..grid.ncols() - 1).map(move |c| (r, c)))
-        .filter(|&(r, c)| grid[[r, c]] == 'A') // center must be A
-        .filter(|&(r, c)| {
+        .filter(|&idx| grid[idx] == 'A') // center must be A
+        .map(Vec2::from_index)
+        .filter(|&center| {
             // collect and validate diag chars
-            diagonals.iter().all(|&(dr, dc)| {
-                let chars: Vec<_> = (0..3)
-                    .map(|i| {
-                        let row = (r as isize + dr * (i - 1)) as usize;
-                        let col = (c as isize + dc * (i - 1)) as usize;
-                        grid[[row, col]]
-                    })
+            diagonals.iter().all(|&dir| {
+                let chars: Vec<_> = [center + dir, center, center - dir]
+                    .iter()
+                    .filter_map(|p| p.to_index(grid.ncols(), grid.nrows()))
+                    .map(|idx| grid[idx])
                     .collect();
                 chars == ['M', 'A', 'S'] || chars == ['S', 'A', 'M']
             })
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(5);
+use advent_of_code::parse::{self, ParseResult};
+
+advent_of_code::solution!(5, validate = validate);
 
 #[derive(Debug)]
 struct Rule {
@@ -72,52 +74,65 @@ fn is_valid_update(update_pages: &[u32], rules: &Vec<Rule>) -> bool {
     true
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (orders, instrs) = input.split_once("\n\n").unwrap();
+/// Parse the `X|Y` rules and the comma-separated updates.
+fn parse_input(input: &str) -> ParseResult<(Vec<Rule>, Vec<Vec<u32>>)> {
+    let [orders, updates] = parse::sections_exact::<2>(input)?;
 
-    // parse the orders
-    let rules: Vec<Rule> = orders
-        .lines()
-        .filter_map(|o| {
-            o.split_once("|").map(|(num, before)| Rule {
-                page: num.parse().unwrap(),
-                before: before.parse().unwrap(),
-            })
+    let rules = orders
+        .iter()
+        .map(|line| {
+            let (page, before) = line.tuple("|")?;
+            Ok(Rule { page, before })
         })
-        .collect();
+        .collect::<ParseResult<_>>()?;
 
-    let sum_middle = instrs.lines().fold(0, |acc, update| {
-        let pages: Vec<u32> = update.split(',').filter_map(|c| c.parse().ok()).collect();
+    let updates = updates
+        .iter()
+        .map(|line| line.split(","))
+        .collect::<ParseResult<_>>()?;
 
-        if is_valid_update(&pages, &rules) {
+    Ok((rules, updates))
+}
+
+/// Every update needs a middle page.
+fn validate(input: &str) -> ParseResult<()> {
+    let [_, updates] = parse::sections_exact::<2>(input)?;
+
+    for line in updates {
+        let pages: Vec<u32> = line.split(",")?;
+        if pages.len().is_multiple_of(2) {
+            return Err(line.error(1, "expected an odd number of pages"));
+        }
+    }
+
+    parse_input(input).map(|_| ())
+}
+
+pub fn part_one(input: &str) -> ParseResult<u32> {
+    let (rules, updates) = parse_input(input)?;
+
+    let sum_middle = updates.iter().fold(0, |acc, pages| {
+        if is_valid_update(pages, &rules) {
             acc + pages.get(pages.len() / 2).unwrap()
         } else {
             acc
         }
     });
 
-    Some(sum_middle)
+    Ok(sum_middle)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (orders, updates) = input.split_once("\n\n").unwrap();
+pub fn part_two(input: &str) -> ParseResult<u32> {
+    let (rules, updates) = parse_input(input)?;
 
     // (page_num, Vec<pages we need to be before>)
     let mut page_rules: HashMap<u32, Vec<u32>> = HashMap::new();
-    let mut rules: Vec<Rule> = Vec::new();
-
-    for page in orders.lines() {
-        if let Some((num, before)) = page.split_once('|') {
-            let num: u32 = num.parse().unwrap();
-            let before: u32 = before.parse().unwrap();
-            page_rules.entry(num).or_default().push(before);
-            rules.push(Rule { page: num, before });
-        }
-    }
 
-    let sum_middle = updates.lines().fold(0, |acc, update| {
-        let pages: Vec<u32> = update.split(',').filter_map(|c| c.parse().ok()).collect();
+    for rule in &rules {
+        page_rules.entry(rule.page).or_default().push(rule.before);
+    }
 
+    let sum_middle = updates.iter().fold(0, |acc, pages| {
         let mut page_structs: Vec<Page> = pages
             .iter()
             .map(|&page_number| {
@@ -132,7 +147,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             .collect();
 
         // check if valid; if not, sort
-        if is_valid_update(&pages, &rules) {
+        if is_valid_update(pages, &rules) {
             acc
         } else {
             page_structs.sort();
@@ -144,7 +159,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     });
 
-    Some(sum_middle)
+    Ok(sum_middle)
 }
 
 #[cfg(test)]
@@ -154,12 +169,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
+        assert_eq!(result, Ok(143));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        assert_eq!(result, Ok(123));
     }
 }
@@ -1,185 +1,195 @@
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, validate = validate);
 
-use ndarray::Array2;
+use advent_of_code::geometry::{Direction, Vec2};
+use advent_of_code::parse::{self, ParseError, ParseResult};
 use rayon::prelude::*;
-use std::collections::HashSet;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    // parse to grid, strip newlines
-    let flat_grid: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
-
-    // grid is square
-    let side_len = (flat_grid.len() as f64).sqrt() as usize;
-
-    let grid = Array2::from_shape_vec((side_len, side_len), flat_grid).unwrap();
-
-    let mut start_pos = None;
-    let start_dir = '^';
-
-    // find starting position, only starts ^ (for my case at least)
-    for ((y, x), &value) in grid.indexed_iter() {
-        if value == start_dir {
-            start_pos = Some((x as isize, y as isize)); // (col, row)
-            break;
+/// The map is a square grid of `.` and `#` with exactly one guard `^`.
+fn validate(input: &str) -> ParseResult<()> {
+    let size = input.lines().count();
+    let mut guard = None;
+
+    for line in parse::lines(input) {
+        if line.text.len() != size {
+            return Err(line.error(
+                line.text.len().min(size) + 1,
+                format_args!("expected {size} columns, found {}", line.text.len()),
+            ));
         }
-    }
-
-    let start_pos = start_pos.unwrap();
-
-    // series of right turns n -> w -> s -> e
-    let moves = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-    let mut visited: HashSet<(isize, isize)> = HashSet::new();
 
-    visited.insert(start_pos);
-
-    let mut cur_pos = start_pos;
-    let mut dir_idx = 0;
-
-    loop {
-        // find next spot
-        let delta = moves[dir_idx];
-        let next_pos = (cur_pos.0 + delta.0, cur_pos.1 + delta.1);
-
-        // check if leaving areas
-        if next_pos.0 < 0
-            || next_pos.1 < 0
-            || next_pos.0 >= side_len as isize
-            || next_pos.1 >= side_len as isize
-        {
-            break;
-        }
-
-        // check obstacle
-        if grid[[next_pos.1 as usize, next_pos.0 as usize]] == '#' {
-            // turn right
-            dir_idx = (dir_idx + 1) % 4;
-        } else {
-            // go forward
-            cur_pos = next_pos;
-            visited.insert(cur_pos);
+        for (i, c) in line.text.char_indices() {
+            match c {
+                '.' | '#' => {}
+                '^' if guard.is_none() => guard = Some(line.number),
+                '^' => return Err(line.error(i + 1, "found more than one guard")),
+                c => return Err(line.error(i + 1, format_args!("unexpected `{c}`"))),
+            }
         }
     }
 
-    Some(visited.len() as u32)
+    match guard {
+        Some(_) => Ok(()),
+        None => Err(ParseError {
+            line: size,
+            column: 1,
+            message: "found no guard `^`".into(),
+        }),
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    // parse to grid, strip newlines
-    let flat_grid: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
-
-    // grid is square
-    let side_len = (flat_grid.len() as f64).sqrt() as usize;
+/// The lab, with the obstacle that the guard runs into from every cell in every direction.
+struct Lab {
+    size: usize,
+    walls: Vec<bool>,
+    start: Vec2,
+    /// The next obstacle ahead, or the first point off the map, indexed by [`Lab::state`].
+    blockers: Vec<Vec2>,
+}
 
-    let grid = Array2::from_shape_vec((side_len, side_len), flat_grid).unwrap();
+impl Lab {
+    fn parse(input: &str) -> Self {
+        let size = input.lines().count();
+        let mut walls = Vec::with_capacity(size * size);
+        let mut start = Vec2::new(0, 0);
+
+        for (row, line) in input.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                if c == '^' {
+                    start = Vec2::from_index((row, col));
+                }
+                walls.push(c == '#');
+            }
+        }
 
-    let mut start_pos = None;
-    let start_dir = '^';
+        let mut lab = Self {
+            size,
+            walls,
+            start,
+            blockers: vec![Vec2::new(0, 0); size * size * 4],
+        };
+
+        // the blocker of a cell is its neighbor if that is an obstacle, otherwise the blocker of the neighbor.
+        // going away from the direction, the neighbor is always done first.
+        for dir in Direction::ALL {
+            let mut cells: Vec<usize> = (0..size * size).collect();
+            if matches!(dir, Direction::South | Direction::East) {
+                cells.reverse();
+            }
 
-    // find starting position, only starts ^ (for my case at least)
-    for ((y, x), &value) in grid.indexed_iter() {
-        if value == start_dir {
-            start_pos = Some((x as isize, y as isize)); // (col, row)
-            break;
+            for i in cells {
+                let pos = Vec2::from_index((i / size, i % size));
+                let next = pos + dir.delta();
+                let blocker = match lab.cell(next) {
+                    Some(j) if !lab.walls[j] => lab.blockers[lab.state(next, dir)],
+                    _ => next,
+                };
+                let state = lab.state(pos, dir);
+                lab.blockers[state] = blocker;
+            }
         }
-    }
 
-    let start_pos = start_pos.unwrap();
+        lab
+    }
 
-    // series of right turns n -> w -> s -> e
-    let moves = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-    let mut visited: HashSet<(isize, isize)> = HashSet::new();
+    fn cell(&self, pos: Vec2) -> Option<usize> {
+        pos.to_flat_index(self.size, self.size)
+    }
 
-    visited.insert(start_pos);
+    /// The index of a position on the map and a direction, in `0..size * size * 4`.
+    fn state(&self, pos: Vec2, dir: Direction) -> usize {
+        pos.to_flat_index(self.size, self.size).unwrap() * 4 + dir.index()
+    }
 
-    let mut cur_pos = start_pos;
-    let mut dir_idx = 0;
+    /// Walk the guard step by step without extra obstructions. Returns the position and direction of the guard just
+    /// before stepping on each cell for the first time, i.e. every cell of the path except the start.
+    fn patrol(&self) -> Vec<(Vec2, Direction)> {
+        let mut seen = vec![false; self.walls.len()];
+        seen[self.cell(self.start).unwrap()] = true;
 
-    loop {
-        // find next spot
-        let delta = moves[dir_idx];
-        let next_pos = (cur_pos.0 + delta.0, cur_pos.1 + delta.1);
+        let mut path = vec![];
+        let (mut pos, mut dir) = (self.start, Direction::North);
 
-        // check if leaving areas
-        if next_pos.0 < 0
-            || next_pos.1 < 0
-            || next_pos.0 >= side_len as isize
-            || next_pos.1 >= side_len as isize
-        {
-            break;
+        while let Some(next) = self.cell(pos + dir.delta()) {
+            if self.walls[next] {
+                dir = dir.turn_right();
+            } else {
+                if !seen[next] {
+                    seen[next] = true;
+                    path.push((pos, dir));
+                }
+                pos += dir.delta();
+            }
         }
 
-        // check obstacle
-        if grid[[next_pos.1 as usize, next_pos.0 as usize]] == '#' {
-            // turn right
-            dir_idx = (dir_idx + 1) % 4;
-        } else {
-            // go forward
-            cur_pos = next_pos;
-            visited.insert(cur_pos);
-        }
+        path
     }
 
-    // rayon parallel iter
-    let obstruction_spots: u32 = visited
-        .par_iter()
-        .filter_map(|&pos| {
-            // skip obstacles or start pos
-            let obstruction = (pos.1 as usize, pos.0 as usize);
-            if grid[[obstruction.0, obstruction.1]] == '#' || pos == start_pos {
-                return None;
+    /// Whether the guard, standing at `pos` in front of the `obstruction` in direction `dir`, walks in a loop. Jumps
+    /// from obstacle to obstacle and marks the states in front of them in `visited`, which must be cleared.
+    fn loops(
+        &self,
+        mut pos: Vec2,
+        mut dir: Direction,
+        obstruction: Vec2,
+        visited: &mut [u64],
+    ) -> bool {
+        loop {
+            let state = self.state(pos, dir);
+            let bit = 1 << (state % 64);
+            if visited[state / 64] & bit != 0 {
+                return true;
             }
+            visited[state / 64] |= bit;
 
-            if simulate_guard(&grid, start_pos, &moves, side_len, obstruction) {
-                Some(1)
-            } else {
-                None
+            dir = dir.turn_right();
+            let mut blocker = self.blockers[self.state(pos, dir)];
+
+            // the obstruction is not in the table, it is hit if it comes first.
+            let distance = pos.manhattan(obstruction);
+            if pos + dir.delta() * distance == obstruction && distance < pos.manhattan(blocker) {
+                blocker = obstruction;
             }
-        })
-        .sum();
 
-    Some(obstruction_spots)
+            if self.cell(blocker).is_none() {
+                return false;
+            }
+            pos = blocker - dir.delta();
+        }
+    }
 }
 
-fn simulate_guard(
-    grid: &Array2<char>,
-    start_pos: (isize, isize),
-    moves: &[(isize, isize)],
-    side_len: usize,
-    test_obstruction: (usize, usize),
-) -> bool {
-    let mut visited: HashSet<((isize, isize), usize)> = HashSet::new();
-    let mut cur_pos = start_pos;
-    let mut dir_idx = 0;
-
-    loop {
-        if visited.contains(&(cur_pos, dir_idx)) {
-            return true; // loop detected
-        }
-        visited.insert((cur_pos, dir_idx));
+pub fn part_one(input: &str) -> Option<u32> {
+    let lab = Lab::parse(input);
 
-        let delta = moves[dir_idx];
-        let next_pos = (cur_pos.0 + delta.0, cur_pos.1 + delta.1);
+    // the path does not include the start.
+    Some(lab.patrol().len() as u32 + 1)
+}
 
-        if next_pos.0 < 0
-            || next_pos.1 < 0
-            || next_pos.0 >= side_len as isize
-            || next_pos.1 >= side_len as isize
-        {
-            return false; //off grid
-        }
+pub fn part_two(input: &str) -> Option<u32> {
+    let lab = Lab::parse(input);
 
-        if grid[[next_pos.1 as usize, next_pos.0 as usize]] == '#'
-            || (next_pos.1 as usize, next_pos.0 as usize) == test_obstruction
-        {
-            dir_idx = (dir_idx + 1) % 4;
-        } else {
-            cur_pos = next_pos;
-        }
-    }
+    // the guard walks the original path until the obstruction is in front of them for the first time, so the
+    // simulation starts right there.
+    let obstruction_spots = lab
+        .patrol()
+        .par_iter()
+        .map_init(
+            || vec![0u64; lab.blockers.len().div_ceil(64)],
+            |visited, &(pos, dir)| {
+                visited.fill(0);
+                lab.loops(pos, dir, pos + dir.delta(), visited)
+            },
+        )
+        .filter(|&loops| loops)
+        .count();
+
+    Some(obstruction_spots as u32)
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::rng::XorShift;
+
     use super::*;
 
     #[test]
@@ -193,4 +203,64 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_validate() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(validate(&input), Ok(()));
+
+        let err = validate(&input.replacen('.', "^", 1)).unwrap_err();
+        assert_eq!((err.line, err.column), (7, 5));
+        let err = validate(&input[..input.len() - 1]).unwrap_err();
+        assert_eq!((err.line, err.column), (10, 10));
+    }
+
+    /// Step cell by cell, as the solution used to, and tell whether the guard leaves the map.
+    fn walks_out(lab: &Lab, obstruction: Vec2) -> bool {
+        let mut visited = std::collections::HashSet::new();
+        let (mut pos, mut dir) = (lab.start, Direction::North);
+
+        while visited.insert((pos, dir)) {
+            let next = pos + dir.delta();
+            match lab.cell(next) {
+                None => return true,
+                Some(j) if lab.walls[j] || next == obstruction => dir = dir.turn_right(),
+                Some(_) => pos = next,
+            }
+        }
+
+        false
+    }
+
+    #[test]
+    fn test_part_two_matches_steps() {
+        let mut rng = XorShift::new(0x2024_0006);
+        for _ in 0..200 {
+            let size = 3 + rng.below(10);
+            let guard = rng.below(size * size);
+            let mut input = String::new();
+            for i in 0..size * size {
+                input.push(match i {
+                    _ if i == guard => '^',
+                    _ if rng.below(6) == 0 => '#',
+                    _ => '.',
+                });
+                if i % size == size - 1 {
+                    input.push('\n');
+                }
+            }
+
+            // puzzle inputs let the guard leave the map.
+            let lab = Lab::parse(&input);
+            if !walks_out(&lab, Vec2::new(-1, -1)) {
+                continue;
+            }
+
+            let loops = (0..size * size)
+                .map(|i| Vec2::from_index((i / size, i % size)))
+                .filter(|&pos| pos != lab.start && !walks_out(&lab, pos))
+                .count();
+            assert_eq!(part_two(&input), Some(loops as u32), "\n{input}");
+        }
+    }
 }
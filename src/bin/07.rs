@@ -1,20 +1,23 @@
 advent_of_code::solution!(7);
 
+use advent_of_code::math;
+use advent_of_code::parse::{self, Line, ParseResult};
+use advent_of_code::template::SolutionResult;
 use rayon::prelude::*;
 
-type Operator = fn(usize, usize) -> usize;
+/// Operators return `None` on overflow, which can never reach a test value.
+type Operator = fn(usize, usize) -> Option<usize>;
 
-fn operator_add(a: usize, b: usize) -> usize {
-    a + b
+fn operator_add(a: usize, b: usize) -> Option<usize> {
+    a.checked_add(b)
 }
 
-fn operator_concatenate(a: usize, b: usize) -> usize {
-    let digits: usize = (b as f64).log10().floor() as usize + 1;
-    a * 10usize.pow(digits as u32) + b
+fn operator_concatenate(a: usize, b: usize) -> Option<usize> {
+    math::concat(a, b)
 }
 
-fn operator_multiply(a: usize, b: usize) -> usize {
-    a * b
+fn operator_multiply(a: usize, b: usize) -> Option<usize> {
+    a.checked_mul(b)
 }
 
 #[derive(Debug)]
@@ -25,19 +28,11 @@ struct Equation {
 }
 
 impl Equation {
-    fn new(input: &str) -> Option<Self> {
-        let (ans, remaining) = input.split_once(": ").unwrap();
-        let ans: usize = match ans.parse() {
-            Ok(ans) => ans,
-            Err(_) => {
-                eprintln!("Bad Line: {}", input);
-                return None;
-            }
-        };
-        let ops: Vec<usize> = remaining.split(" ").map(|op| op.parse().unwrap()).collect();
-        Some(Self {
+    fn new(line: Line) -> ParseResult<Self> {
+        let (test_value, ops) = line.key_values(":")?;
+        Ok(Self {
             solutions: 0,
-            test_value: ans,
+            test_value,
             ops,
         })
     }
@@ -71,37 +66,37 @@ impl Equation {
         }
 
         operators.iter().for_each(|&operator| {
-            self.solve(
-                operators,
-                operator(current_result, self.ops[index]),
-                index + 1,
-            );
+            if let Some(result) = operator(current_result, self.ops[index]) {
+                self.solve(operators, result, index + 1);
+            }
         });
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str) -> SolutionResult<usize> {
     // process lines in to equations
-    let equs: Vec<Equation> = input.lines().filter_map(Equation::new).collect();
+    let equs: Vec<Equation> = parse::lines(input)
+        .map(Equation::new)
+        .collect::<ParseResult<_>>()?;
 
     // parallel iterate to check if valid
-    Some(
-        equs.into_par_iter()
-            .fold(|| 0_usize, |acc, mut equ| equ.is_valid(false) + acc)
-            .sum::<usize>(),
-    )
+    Ok(equs
+        .into_par_iter()
+        .fold(|| 0_usize, |acc, mut equ| equ.is_valid(false) + acc)
+        .sum::<usize>())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str) -> SolutionResult<usize> {
     // process lines in to equations
-    let equs: Vec<Equation> = input.lines().filter_map(Equation::new).collect();
+    let equs: Vec<Equation> = parse::lines(input)
+        .map(Equation::new)
+        .collect::<ParseResult<_>>()?;
 
     // parallel iterate to check if valid
-    Some(
-        equs.into_par_iter()
-            .fold(|| 0_usize, |acc, mut equ| equ.is_valid(true) + acc)
-            .sum::<usize>(),
-    )
+    Ok(equs
+        .into_par_iter()
+        .fold(|| 0_usize, |acc, mut equ| equ.is_valid(true) + acc)
+        .sum::<usize>())
 }
 
 #[cfg(test)]
@@ -111,12 +106,28 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
+        assert_eq!(result.ok(), Some(3749));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
+        assert_eq!(result.ok(), Some(11387));
+    }
+
+    #[test]
+    fn test_operators() {
+        assert_eq!(operator_concatenate(15, 0), Some(150));
+        assert_eq!(operator_concatenate(12, 345), Some(12345));
+        assert_eq!(operator_concatenate(usize::MAX / 10, 99), None);
+        assert_eq!(operator_multiply(usize::MAX, 2), None);
+    }
+
+    #[test]
+    fn test_bad_line() {
+        let result = part_one("190: 10 19\n3267 81 40 27\n");
+        let err = result.unwrap_err();
+        assert!(err.message().starts_with("line 2, column 14:"));
+        assert!(err.location().file().ends_with("07.rs"));
     }
 }
@@ -1,17 +1,16 @@
 advent_of_code::solution!(8);
 
+use advent_of_code::geometry::Vec2;
+use advent_of_code::math;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
 use ndarray::Array2;
 
 #[allow(unused)]
-fn print_map(
-    grid: &ndarray::ArrayBase<ndarray::OwnedRepr<char>, ndarray::Dim<[usize; 2]>>,
-    antinodes: &HashSet<(usize, usize)>,
-) {
+fn print_map(grid: &Array2<char>, antinodes: &HashSet<Vec2>) {
     for (coord, c) in grid.indexed_iter() {
-        if antinodes.get(&coord).is_some() && c == &'.' {
+        if antinodes.contains(&Vec2::from_index(coord)) && c == &'.' {
             print!("#")
         } else {
             print!("{}", c)
@@ -22,43 +21,34 @@ fn print_map(
     }
 }
 
-fn get_distance_antinode(a: &(usize, usize), b: &(usize, usize)) -> Vec<(usize, usize)> {
+fn get_distance_antinode(a: Vec2, b: Vec2) -> [Vec2; 2] {
     // vector diff between antenna
-    let diff_row = b.0 as isize - a.0 as isize;
-    let diff_col = b.1 as isize - a.1 as isize;
+    let diff = b - a;
 
-    // calc antinodes
-    let x1 = (a.0 as isize - diff_row, a.1 as isize - diff_col);
-    let x2 = (b.0 as isize + diff_row, b.1 as isize + diff_col);
-
-    vec![
-        (x1.0 as usize, x1.1 as usize),
-        (x2.0 as usize, x2.1 as usize),
-    ]
+    // calc antinodes, these may be off grid
+    [a - diff, b + diff]
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
     let flat_grid: Vec<char> = input.chars().filter(|c| !c.is_ascii_whitespace()).collect();
     let side_len = (flat_grid.len() as f64).sqrt() as usize;
 
-    let grid: ndarray::ArrayBase<ndarray::OwnedRepr<char>, ndarray::Dim<[usize; 2]>> =
-        Array2::from_shape_vec((side_len, side_len), flat_grid).unwrap();
+    let grid = Array2::from_shape_vec((side_len, side_len), flat_grid).unwrap();
 
-    let mut freqs: HashMap<char, Vec<(usize, usize)>> = HashMap::new();
-    for ((row, col), c) in grid.indexed_iter() {
+    let mut freqs: HashMap<char, Vec<Vec2>> = HashMap::new();
+    for (idx, c) in grid.indexed_iter() {
         if *c != '.' {
-            freqs.entry(*c).or_default().push((row, col));
+            freqs.entry(*c).or_default().push(Vec2::from_index(idx));
         }
     }
 
-    let mut antinodes_set: HashSet<(usize, usize)> = HashSet::new();
+    let mut antinodes_set: HashSet<Vec2> = HashSet::new();
 
     for (_, antennas) in freqs {
         for (a, b) in antennas.iter().tuple_combinations() {
-            let antinodes = get_distance_antinode(a, b);
+            let antinodes = get_distance_antinode(*a, *b);
             for antinode in antinodes {
-                // use this for ongrid validation - none if oob
-                if grid.get(antinode).is_some() {
+                if antinode.to_index(side_len, side_len).is_some() {
                     antinodes_set.insert(antinode);
                 }
             }
@@ -70,44 +60,27 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(antinodes_set.len())
 }
 
-fn get_diagonal_antinodes(
-    a: &(usize, usize),
-    b: &(usize, usize),
-    grid_size: usize,
-) -> Vec<(usize, usize)> {
+fn get_diagonal_antinodes(a: Vec2, b: Vec2, grid_size: usize) -> Vec<Vec2> {
     let mut points = Vec::new();
 
-    fn gcd(mut a: isize, mut b: isize) -> isize {
-        while b != 0 {
-            let temp = b;
-            b = a % b;
-            a = temp;
-        }
-        a
-    }
-
     // https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
-    let diff_row = b.0 as isize - a.0 as isize;
-    let diff_col = b.1 as isize - a.1 as isize;
-    let gcd = gcd(diff_row.abs(), diff_col.abs());
+    let diff = b - a;
+    let gcd = math::gcd(diff.x, diff.y);
 
-    let step_row = diff_row / gcd;
-    let step_col = diff_col / gcd;
+    let step = Vec2::new(diff.x / gcd, diff.y / gcd);
 
     // dont get full line only get outer diagonal line <- A - B ->
     // extend outward in both directions until we go off grid
-    let mut cur = (a.0 as isize, a.1 as isize);
-    while cur.0 >= 0 && cur.1 >= 0 && cur.0 < grid_size as isize && cur.1 < grid_size as isize {
-        points.push((cur.0 as usize, cur.1 as usize));
-        cur.0 -= step_row;
-        cur.1 -= step_col;
+    let mut cur = a;
+    while cur.to_index(grid_size, grid_size).is_some() {
+        points.push(cur);
+        cur -= step;
     }
 
-    cur = (b.0 as isize, b.1 as isize);
-    while cur.0 >= 0 && cur.1 >= 0 && cur.0 < grid_size as isize && cur.1 < grid_size as isize {
-        points.push((cur.0 as usize, cur.1 as usize));
-        cur.0 += step_row;
-        cur.1 += step_col;
+    cur = b;
+    while cur.to_index(grid_size, grid_size).is_some() {
+        points.push(cur);
+        cur += step;
     }
 
     points
@@ -119,18 +92,18 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     let grid = Array2::from_shape_vec((side_len, side_len), flat_grid).unwrap();
 
-    let mut freqs: HashMap<char, Vec<(usize, usize)>> = HashMap::new();
-    for ((row, col), c) in grid.indexed_iter() {
+    let mut freqs: HashMap<char, Vec<Vec2>> = HashMap::new();
+    for (idx, c) in grid.indexed_iter() {
         if *c != '.' {
-            freqs.entry(*c).or_default().push((row, col));
+            freqs.entry(*c).or_default().push(Vec2::from_index(idx));
         }
     }
 
-    let mut antinodes_set: HashSet<(usize, usize)> = HashSet::new();
+    let mut antinodes_set: HashSet<Vec2> = HashSet::new();
 
     for (_, antennas) in freqs {
         for (a, b) in antennas.iter().tuple_combinations() {
-            let antinodes = get_diagonal_antinodes(a, b, side_len);
+            let antinodes = get_diagonal_antinodes(*a, *b, side_len);
             for antinode in antinodes {
                 antinodes_set.insert(antinode);
             }
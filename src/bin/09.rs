@@ -1,6 +1,9 @@
-use std::collections::{HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
 
-advent_of_code::solution!(9);
+use advent_of_code::parse::{self, ParseResult};
+
+advent_of_code::solution!(9, validate = validate);
 
 #[derive(Debug, Clone, Copy)]
 enum Block {
@@ -13,21 +16,19 @@ struct FileBlock {
     id: usize,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum SizedBlock {
-    File(SizedFileBlock),
-    Free(SizedFreeBlock),
-}
+/// The disk map is a single line of digits.
+fn validate(input: &str) -> ParseResult<()> {
+    for line in parse::lines(input) {
+        if line.number > 1 {
+            return Err(line.error(1, "expected a single line"));
+        }
 
-#[derive(Debug, Clone, Copy)]
-struct SizedFileBlock {
-    id: usize,
-    size: usize,
-}
+        if let Some(i) = line.text.find(|c: char| !c.is_ascii_digit()) {
+            return Err(line.error(i + 1, "expected only digits"));
+        }
+    }
 
-#[derive(Debug, Clone, Copy)]
-struct SizedFreeBlock {
-    size: usize,
+    Ok(())
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -87,106 +88,181 @@ pub fn part_one(input: &str) -> Option<usize> {
     )
 }
 
+/// Free spans of each size, as min-heaps of their start positions. A file of size `s` moves to the leftmost span of
+/// the heaps `s..=9`, and what is left of the span goes to the heap of the remaining size. Spans that files leave
+/// behind are never reused: every file that is still to be moved lies to the left of them.
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut blocks: VecDeque<SizedBlock> = VecDeque::with_capacity(input.len());
+    let mut files: Vec<(usize, usize)> = Vec::with_capacity(input.len() / 2 + 1);
+    let mut free: [BinaryHeap<Reverse<usize>>; 10] = Default::default();
 
-    let mut id = 0;
-    let mut is_file = true;
-    for size in input.chars() {
-        if !size.is_ascii_digit() {
-            break;
+    let mut position = 0;
+    for (i, digit) in input.bytes().take_while(u8::is_ascii_digit).enumerate() {
+        let size = usize::from(digit - b'0');
+        if i % 2 == 0 {
+            files.push((position, size));
+        } else if size > 0 {
+            free[size].push(Reverse(position));
         }
-        let length = size.to_digit(10).unwrap() as usize;
-        if is_file {
-            blocks.push_back(SizedBlock::File(SizedFileBlock { id, size: length }));
-            id += 1;
-        } else {
-            blocks.push_back(SizedBlock::Free(SizedFreeBlock { size: length }));
+        position += size;
+    }
+
+    let mut checksum = 0;
+
+    for (id, &(start, size)) in files.iter().enumerate().rev() {
+        if size == 0 {
+            continue;
         }
-        is_file = !is_file;
+
+        let span = (size..free.len())
+            .filter_map(|span_size| {
+                let &Reverse(span_start) = free[span_size].peek()?;
+                (span_start < start).then_some((span_start, span_size))
+            })
+            .min();
+
+        let start = match span {
+            Some((span_start, span_size)) => {
+                free[span_size].pop();
+                if span_size > size {
+                    free[span_size - size].push(Reverse(span_start + size));
+                }
+                span_start
+            }
+            None => start,
+        };
+
+        // the file covers `start..start + size`, the positions add up to `size * start + size * (size - 1) / 2`.
+        checksum += id * (size * start + size * (size - 1) / 2);
     }
 
-    let mut checked_ids: HashSet<usize> = HashSet::new();
-    let mut read_index = blocks.len() as isize - 1;
+    Some(checksum)
+}
 
-    while read_index >= 0 {
-        let cur_file = match blocks[read_index as usize] {
-            SizedBlock::File(file_block) => file_block,
-            _ => {
+/// The original block-by-block implementation of part two, which the free-span index is tested against.
+#[cfg(test)]
+mod reference {
+    use std::collections::{HashSet, VecDeque};
+
+    #[derive(Debug, Clone, Copy)]
+    enum SizedBlock {
+        File(SizedFileBlock),
+        Free(SizedFreeBlock),
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    struct SizedFileBlock {
+        id: usize,
+        size: usize,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    struct SizedFreeBlock {
+        size: usize,
+    }
+
+    pub fn part_two(input: &str) -> Option<usize> {
+        let mut blocks: VecDeque<SizedBlock> = VecDeque::with_capacity(input.len());
+
+        let mut id = 0;
+        let mut is_file = true;
+        for size in input.chars() {
+            if !size.is_ascii_digit() {
+                break;
+            }
+            let length = size.to_digit(10).unwrap() as usize;
+            if is_file {
+                blocks.push_back(SizedBlock::File(SizedFileBlock { id, size: length }));
+                id += 1;
+            } else {
+                blocks.push_back(SizedBlock::Free(SizedFreeBlock { size: length }));
+            }
+            is_file = !is_file;
+        }
+
+        let mut checked_ids: HashSet<usize> = HashSet::new();
+        let mut read_index = blocks.len() as isize - 1;
+
+        while read_index >= 0 {
+            let cur_file = match blocks[read_index as usize] {
+                SizedBlock::File(file_block) => file_block,
+                _ => {
+                    read_index -= 1;
+                    continue;
+                }
+            };
+
+            if !checked_ids.insert(cur_file.id) {
                 read_index -= 1;
                 continue;
             }
-        };
 
-        if !checked_ids.insert(cur_file.id) {
-            read_index -= 1;
-            continue;
-        }
+            let mut write_index = 0;
+            while write_index < blocks.len() {
+                if let SizedBlock::Free(free_block) = blocks[write_index] {
+                    // can contain whole file
+                    if free_block.size >= cur_file.size && write_index < read_index as usize {
+                        // move file
+                        blocks[write_index] = SizedBlock::File(cur_file);
+                        let mut adjust = false;
 
-        let mut write_index = 0;
-        while write_index < blocks.len() {
-            if let SizedBlock::Free(free_block) = blocks[write_index] {
-                // can contain whole file
-                if free_block.size >= cur_file.size && write_index < read_index as usize {
-                    // move file
-                    blocks[write_index] = SizedBlock::File(cur_file);
-                    let mut adjust = false;
-
-                    // reduce free size if extra
-                    if free_block.size > cur_file.size {
-                        blocks.insert(
-                            write_index + 1,
-                            SizedBlock::Free(SizedFreeBlock {
-                                size: free_block.size - cur_file.size,
-                            }),
-                        );
-
-                        // we insert, need to adjust
-                        read_index += 1;
-                        adjust = true;
-                    }
+                        // reduce free size if extra
+                        if free_block.size > cur_file.size {
+                            blocks.insert(
+                                write_index + 1,
+                                SizedBlock::Free(SizedFreeBlock {
+                                    size: free_block.size - cur_file.size,
+                                }),
+                            );
 
-                    // free original space
-                    blocks[read_index as usize] = SizedBlock::Free(SizedFreeBlock {
-                        size: cur_file.size,
-                    });
+                            // we insert, need to adjust
+                            read_index += 1;
+                            adjust = true;
+                        }
 
-                    // account for an insert
-                    if adjust {
-                        read_index += 1
-                    }
+                        // free original space
+                        blocks[read_index as usize] = SizedBlock::Free(SizedFreeBlock {
+                            size: cur_file.size,
+                        });
 
-                    break;
+                        // account for an insert
+                        if adjust {
+                            read_index += 1
+                        }
+
+                        break;
+                    }
                 }
+                write_index += 1;
             }
-            write_index += 1;
-        }
 
-        read_index -= 1;
-    }
+            read_index -= 1;
+        }
 
-    let mut checksum = 0;
-    let mut current_position = 0;
+        let mut checksum = 0;
+        let mut current_position = 0;
 
-    for block in blocks {
-        match block {
-            SizedBlock::File(file_block) => {
-                for offset in 0..file_block.size {
-                    checksum += (current_position + offset) * file_block.id;
+        for block in blocks {
+            match block {
+                SizedBlock::File(file_block) => {
+                    for offset in 0..file_block.size {
+                        checksum += (current_position + offset) * file_block.id;
+                    }
+                    current_position += file_block.size;
+                }
+                SizedBlock::Free(free_block) => {
+                    current_position += free_block.size;
                 }
-                current_position += file_block.size;
-            }
-            SizedBlock::Free(free_block) => {
-                current_position += free_block.size;
             }
         }
-    }
 
-    Some(checksum)
+        Some(checksum)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::rng::XorShift;
+
     use super::*;
 
     #[test]
@@ -209,4 +285,22 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2858));
     }
+
+    #[test]
+    fn test_part_two_matches_reference() {
+        for input in [
+            advent_of_code::template::read_file("examples", DAY),
+            advent_of_code::template::read_file_part("examples", DAY, 2),
+        ] {
+            assert_eq!(part_two(&input), reference::part_two(&input));
+        }
+
+        let mut rng = XorShift::new(0x2024_0009);
+        for len in 0..300 {
+            let input: String = (0..len)
+                .map(|_| char::from(b'0' + rng.below(10) as u8))
+                .collect();
+            assert_eq!(part_two(&input), reference::part_two(&input), "{input}");
+        }
+    }
 }
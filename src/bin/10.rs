@@ -1,32 +1,46 @@
 advent_of_code::solution!(10);
 
-use pathfinding::prelude::{bfs_reach, count_paths, Matrix};
+use advent_of_code::geometry::Vec2;
+use advent_of_code::search::{bfs_tree, count_paths};
+use ndarray::Array2;
+
+fn parse_grid(input: &str) -> Array2<u32> {
+    let flat_grid: Vec<u32> = input.chars().filter_map(|c| c.to_digit(10)).collect();
+
+    // grid is square
+    let side_len = (flat_grid.len() as f64).sqrt() as usize;
+
+    Array2::from_shape_vec((side_len, side_len), flat_grid).unwrap()
+}
+
+fn height(grid: &Array2<u32>, pos: Vec2) -> Option<u32> {
+    pos.to_index(grid.ncols(), grid.nrows())
+        .map(|idx| grid[idx])
+}
+
+/// Trailheads are the positions with height 0.
+fn trailheads(grid: &Array2<u32>) -> impl Iterator<Item = Vec2> + '_ {
+    grid.indexed_iter()
+        .filter(|&(_, &v)| v == 0)
+        .map(|(idx, _)| Vec2::from_index(idx))
+}
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let grid =
-        Matrix::square_from_vec(input.chars().filter_map(|c| c.to_digit(10)).collect()).unwrap();
-
-    let get_neighbors = |&(x, y): &(usize, usize)| -> Vec<(usize, usize)> {
-        let cur = match grid.get((x, y)) {
-            Some(v) => v,
-            None => return vec![],
-        };
-
-        grid.neighbours((x, y), false)
-            .filter(|&x| Some(cur + 1) == grid.get(x).copied())
-            .collect()
+    let grid = &parse_grid(input);
+
+    // trails go up by exactly one
+    let get_neighbors = |&pos: &Vec2| {
+        let next = height(grid, pos).map(|h| h + 1);
+        pos.neighbors_4()
+            .filter(move |&n| next.is_some() && height(grid, n) == next)
     };
 
-    let total_paths = grid
-        .items()
-        .map(|((r, c), &v)| {
-            if v == 0 {
-                bfs_reach((r, c), get_neighbors)
-                    .filter(|&(x, y)| grid.get((x, y)) == Some(&9))
-                    .count()
-            } else {
-                0
-            }
+    let total_paths = trailheads(grid)
+        .map(|start| {
+            bfs_tree(start, get_neighbors)
+                .nodes()
+                .filter(|&(&pos, _)| height(grid, pos) == Some(9))
+                .count()
         })
         .sum();
 
@@ -34,29 +48,17 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let grid =
-        Matrix::square_from_vec(input.chars().filter_map(|c| c.to_digit(10)).collect()).unwrap();
-
-    let get_neighbors = |&(x, y): &(usize, usize)| -> Vec<(usize, usize)> {
-        let cur = match grid.get((x, y)) {
-            Some(v) => v,
-            None => return vec![],
-        };
-
-        grid.neighbours((x, y), false)
-            .filter(|&x| Some(cur + 1) == grid.get(x).copied())
-            .collect()
+    let grid = &parse_grid(input);
+
+    // trails go up by exactly one
+    let get_neighbors = |&pos: &Vec2| {
+        let next = height(grid, pos).map(|h| h + 1);
+        pos.neighbors_4()
+            .filter(move |&n| next.is_some() && height(grid, n) == next)
     };
 
-    let total_paths = grid
-        .items()
-        .map(|((r, c), &v)| {
-            if v == 0 {
-                count_paths((r, c), get_neighbors, |&idx| grid.get(idx) == Some(&9))
-            } else {
-                0
-            }
-        })
+    let total_paths = trailheads(grid)
+        .map(|start| count_paths(start, get_neighbors, |&pos| height(grid, pos) == Some(9)))
         .sum();
 
     Some(total_paths)
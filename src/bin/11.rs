@@ -1,48 +1,91 @@
-use cached::proc_macro::cached;
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+use std::sync::OnceLock;
+
+use advent_of_code::math;
+use advent_of_code::template::runner::solution_arg;
 
 advent_of_code::solution!(11);
 
-#[cached]
-fn compute_stone(stone: usize, depth: usize) -> usize {
-    if depth == 0 {
-        return 1;
+/// Number of stones after blinking `blinks` times. `None` if the count does not fit into a `u128`, which happens after
+/// a bit more than 200 blinks; use [`blink_mod`] to go further.
+pub fn blink(input: &str, blinks: usize) -> Option<u128> {
+    evolve(input, blinks, (0, 1), u128::checked_add)
+}
+
+/// Number of stones after blinking `blinks` times, modulo `modulus`. `None` if `modulus` is 0.
+pub fn blink_mod(input: &str, blinks: usize, modulus: u64) -> Option<u64> {
+    let one = 1u64.checked_rem(modulus)?;
+    let add =
+        |a: u64, b: u64| u64::try_from((u128::from(a) + u128::from(b)) % u128::from(modulus)).ok();
+    evolve(input, blinks, (0, one), add)
+}
+
+/// Blink with the count of each distinct stone, starting from `(zero, one)`. Equal stones behave the same, and
+/// there are only a few thousand distinct ones, so the work per blink stays the same while the counts grow
+/// exponentially.
+fn evolve<C: Copy>(
+    input: &str,
+    blinks: usize,
+    (zero, one): (C, C),
+    add: impl Fn(C, C) -> Option<C>,
+) -> Option<C> {
+    let mut stones: HashMap<u64, C> = HashMap::new();
+    let count = |stones: &mut HashMap<u64, C>, stone: u64, n: C| {
+        let total = stones.entry(stone).or_insert(zero);
+        *total = add(*total, n)?;
+        Some(())
+    };
+
+    for stone in input.split_ascii_whitespace() {
+        count(&mut stones, stone.parse().ok()?, one)?;
     }
 
-    let num_digits = (stone as f64).log10().floor() as usize + 1;
+    for _ in 0..blinks {
+        let mut next = HashMap::with_capacity(stones.len());
+        for (stone, n) in stones {
+            let num_digits = math::num_digits(stone);
+
+            if stone == 0 {
+                count(&mut next, 1, n)?;
+            } else if num_digits.is_multiple_of(2) {
+                let (left, right) = math::split_digits(stone, num_digits / 2);
+                count(&mut next, left, n)?;
+                count(&mut next, right, n)?;
+            } else {
+                count(&mut next, stone.checked_mul(2024)?, n)?;
+            }
+        }
+        stones = next;
+    }
 
-    if stone == 0 {
-        compute_stone(1, depth - 1)
-    } else if num_digits % 2 == 0 {
-        let half_len = num_digits / 2;
-        let divisor = 10usize.pow(half_len as u32);
+    stones.into_values().try_fold(zero, &add)
+}
 
-        let left_num = stone / divisor;
-        let right_num = stone % divisor;
+/// Solve with `cargo solve 11 -- --blinks <n>` to change the number of blinks of both parts, and add
+/// `--modulo <m>` to count modulo `m`.
+fn solve(input: &str, blinks: usize) -> Option<u128> {
+    let (blinks_arg, modulo_arg) = solution_args();
+    let blinks = blinks_arg.unwrap_or(blinks);
 
-        compute_stone(left_num, depth - 1) + compute_stone(right_num, depth - 1)
-    } else {
-        compute_stone(stone * 2024, depth - 1)
+    match modulo_arg {
+        Some(modulus) => blink_mod(input, blinks, modulus.get()).map(u128::from),
+        None => blink(input, blinks),
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(
-        input
-            .split_ascii_whitespace()
-            .filter_map(|sval| sval.parse::<usize>().ok())
-            .map(|stone| compute_stone(stone, 25))
-            .sum(),
-    )
+/// The arguments are read once, so benchmark runs do not parse them again. A modulo of 0 is rejected when parsing.
+fn solution_args() -> (Option<usize>, Option<NonZeroU64>) {
+    static ARGS: OnceLock<(Option<usize>, Option<NonZeroU64>)> = OnceLock::new();
+    *ARGS.get_or_init(|| (solution_arg("blinks"), solution_arg("modulo")))
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    Some(
-        input
-            .split_ascii_whitespace()
-            .filter_map(|sval| sval.parse::<usize>().ok())
-            .map(|stone| compute_stone(stone, 75))
-            .sum(),
-    )
+pub fn part_one(input: &str) -> Option<u128> {
+    solve(input, 25)
+}
+
+pub fn part_two(input: &str) -> Option<u128> {
+    solve(input, 75)
 }
 
 #[cfg(test)]
@@ -60,4 +103,26 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(65601038650482));
     }
+
+    #[test]
+    fn test_blink() {
+        assert_eq!(blink("125 17", 0), Some(2));
+        assert_eq!(blink("125 17", 6), Some(22));
+        assert_eq!(blink("0 0 0", 1), Some(3));
+        assert_eq!(blink("", 10), Some(0));
+        assert_eq!(blink("125 17", 1000), None);
+    }
+
+    #[test]
+    fn test_blink_mod() {
+        let modulus = 1_000_000_007;
+        let expected = blink("125 17", 150).unwrap() % u128::from(modulus);
+        assert_eq!(
+            blink_mod("125 17", 150, modulus).map(u128::from),
+            Some(expected)
+        );
+        assert!(blink_mod("125 17", 1000, modulus).is_some());
+        assert_eq!(blink_mod("125 17", 10, 1), Some(0));
+        assert_eq!(blink_mod("125 17", 10, 0), None);
+    }
 }
@@ -1,112 +1,28 @@
 advent_of_code::solution!(12);
 
-use pathfinding::prelude::{bfs_reach, Matrix};
-use std::collections::HashSet;
+use advent_of_code::regions;
+use ndarray::Array2;
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let grid =
-        Matrix::square_from_vec(input.chars().filter(|c| !c.is_ascii_whitespace()).collect())
-            .unwrap();
-
-    let get_neighbors = |&(x, y): &(usize, usize)| -> Vec<(usize, usize)> {
-        let cur = match grid.get((x, y)) {
-            Some(v) => v,
-            None => return vec![],
-        };
-
-        grid.neighbours((x, y), false)
-            .filter(|&x| Some(cur) == grid.get(x))
-            .collect()
-    };
-
-    let mut found: HashSet<(usize, usize)> = HashSet::new();
+fn parse_grid(input: &str) -> Array2<char> {
+    let flat_grid: Vec<char> = input.chars().filter(|c| !c.is_ascii_whitespace()).collect();
 
-    Some(grid.items().fold(0, |mut total_price, ((r, c), _)| {
-        // skip if already in a region
-        if found.contains(&(r, c)) {
-            return total_price;
-        }
+    // grid is square
+    let side_len = (flat_grid.len() as f64).sqrt() as usize;
 
-        // isolate region
-        let region: Vec<(usize, usize)> = bfs_reach((r, c), get_neighbors)
-            .inspect(|&coord| {
-                found.insert(coord);
-            })
-            .collect();
+    Array2::from_shape_vec((side_len, side_len), flat_grid).unwrap()
+}
 
-        // get perimeter for each coordinate: 4 possible perms - number of internal faces
-        for coord in &region {
-            total_price += region.len() * (4 - get_neighbors(coord).len());
-        }
+pub fn part_one(input: &str) -> Option<usize> {
+    let regions = regions::label(&parse_grid(input));
 
-        total_price
-    }))
+    Some(regions.iter().map(|r| r.area * r.perimeter).sum())
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let grid =
-        Matrix::square_from_vec(input.chars().filter(|c| !c.is_ascii_whitespace()).collect())
-            .unwrap();
-
-    let get_neighbors = |&(x, y): &(usize, usize)| -> Vec<(usize, usize)> {
-        let cur = match grid.get((x, y)) {
-            Some(v) => v,
-            None => return vec![],
-        };
-
-        grid.neighbours((x, y), false)
-            .filter(|&neighbor| Some(cur) == grid.get(neighbor))
-            .collect()
-    };
-
-    // an n-sided polygon will have n-corners- need i sizes for subtraction reasons
-    let count_corners = |region: &Vec<(i32, i32)>| -> usize {
-        let dir: [(i32, i32); 4] = [(1, 0), (0, 1), (0, -1), (-1, 0)];
-
-        dir.iter().fold(0, |side_count, dir| {
-            let mut sides = HashSet::new();
-            // detect region boundary side
-            for pos in region {
-                let tmp = (pos.0 + dir.0, pos.1 + dir.1);
-                if !region.contains(&tmp) {
-                    sides.insert(tmp);
-                }
-            }
-
-            // remove linear segments
-            let mut remove: HashSet<(i32, i32)> = HashSet::new();
-            for side in &sides {
-                let mut tmp = (side.0 + dir.1, side.1 + dir.0);
-                // travel linear segment
-                while sides.contains(&tmp) {
-                    remove.insert(tmp);
-                    tmp = (tmp.0 + dir.1, tmp.1 + dir.0); // straight line
-                }
-            }
-
-            side_count + sides.len() - remove.len()
-        })
-    };
-
-    let mut found: HashSet<(usize, usize)> = HashSet::new();
-
-    Some(grid.items().fold(0, |total_price, ((r, c), _)| {
-        // skip if already in a region
-        if found.contains(&(r, c)) {
-            return total_price;
-        }
-
-        // isolate region
-        let region: Vec<(i32, i32)> = bfs_reach((r, c), get_neighbors)
-            .map(|coord| {
-                found.insert(coord);
-                (coord.0 as i32, coord.1 as i32)
-            })
-            .collect();
+    let regions = regions::label(&parse_grid(input));
 
-        let corners = count_corners(&region);
-        total_price + region.len() * corners
-    }))
+    // an n-sided polygon will have n-corners, which is what regions count as sides
+    Some(regions.iter().map(|r| r.area * r.sides).sum())
 }
 
 #[cfg(test)]
@@ -0,0 +1,343 @@
+//! Union-find over elements `0..n`, or over arbitrary hashable keys with [`KeyedDisjointSet`].
+//!
+//! Uses path compression and union by rank, so a sequence of operations runs in nearly linear time.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use ndarray::Array2;
+
+#[derive(Clone, Debug, Default)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// `n` elements, each in its own set.
+    #[must_use]
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            ranks: vec![0; n],
+            sizes: vec![1; n],
+            components: n,
+        }
+    }
+
+    /// Sets of the cells of a grid, where orthogonally adjacent cells with equal values are in the same set.
+    /// Cells are numbered row by row, i.e. `row * width + col`.
+    #[must_use]
+    pub fn from_grid<T: PartialEq>(grid: &Array2<T>) -> Self {
+        Self::from_grid_by(grid.ncols(), grid.nrows(), |a, b| grid[a] == grid[b])
+    }
+
+    /// Sets of the cells of a `width` x `height` grid, where `same` tells whether two orthogonally adjacent
+    /// `(row, col)` cells belong to the same set.
+    pub fn from_grid_by(
+        width: usize,
+        height: usize,
+        same: impl Fn((usize, usize), (usize, usize)) -> bool,
+    ) -> Self {
+        let mut set = Self::new(width * height);
+
+        for row in 0..height {
+            for col in 0..width {
+                let i = row * width + col;
+                if row > 0 && same((row - 1, col), (row, col)) {
+                    set.union(i - width, i);
+                }
+                if col > 0 && same((row, col - 1), (row, col)) {
+                    set.union(i - 1, i);
+                }
+            }
+        }
+
+        set
+    }
+
+    /// Add a new element in its own set and return it.
+    pub fn add(&mut self) -> usize {
+        let element = self.parents.len();
+        self.parents.push(element);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.components += 1;
+        element
+    }
+
+    /// The number of elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The number of disjoint sets.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.components
+    }
+
+    /// The representative of the set that `element` belongs to.
+    pub fn find(&mut self, element: usize) -> usize {
+        let root = self.root(element);
+
+        // point everything on the way directly at the root.
+        let mut cur = element;
+        while cur != root {
+            cur = std::mem::replace(&mut self.parents[cur], root);
+        }
+
+        root
+    }
+
+    /// Like [`DisjointSet::find`], but without compressing paths.
+    #[must_use]
+    pub fn root(&self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            element = self.parents[element];
+        }
+        element
+    }
+
+    /// Merge the sets of `a` and `b`. Returns `false` if they were in the same set already.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (root, child) = if self.ranks[a] < self.ranks[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        self.parents[child] = root;
+        self.sizes[root] += self.sizes[child];
+        if self.ranks[root] == self.ranks[child] {
+            self.ranks[root] += 1;
+        }
+        self.components -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set of `element`.
+    pub fn size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// The representatives of all sets, one per set.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(|&i| self.parents[i] == i)
+    }
+
+    /// The sizes of all sets, one per set.
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.roots().map(|root| self.sizes[root])
+    }
+
+    /// The elements of every set, in order of their smallest element.
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut index: HashMap<usize, usize> = HashMap::new();
+        let mut components: Vec<Vec<usize>> = vec![];
+
+        for element in 0..self.len() {
+            let root = self.root(element);
+            let i = *index.entry(root).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[i].push(element);
+        }
+
+        components
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A [`DisjointSet`] over hashable keys. Keys are added on first use.
+#[derive(Clone, Debug)]
+pub struct KeyedDisjointSet<K> {
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+    set: DisjointSet,
+}
+
+impl<K> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            keys: vec![],
+            set: DisjointSet::default(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> KeyedDisjointSet<K> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `key` in its own set, unless it was added before. Returns its index in the underlying [`DisjointSet`].
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&i) = self.indices.get(&key) {
+            return i;
+        }
+
+        let i = self.set.add();
+        self.indices.insert(key.clone(), i);
+        self.keys.push(key);
+        i
+    }
+
+    #[must_use]
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// The number of keys.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The number of disjoint sets.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.set.count()
+    }
+
+    /// The representative key of the set that `key` belongs to, `None` if `key` was never added.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let i = *self.indices.get(key)?;
+        let root = self.set.find(i);
+        Some(&self.keys[root])
+    }
+
+    /// Merge the sets of `a` and `b`, adding them if needed. Returns `false` if they were in the same set already.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.set.union(a, b)
+    }
+
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&a), Some(&b)) => self.set.same(a, b),
+            _ => false,
+        }
+    }
+
+    /// The number of keys in the set of `key`, 0 if `key` was never added.
+    pub fn size(&mut self, key: &K) -> usize {
+        match self.indices.get(key) {
+            Some(&i) => self.set.size(i),
+            None => 0,
+        }
+    }
+
+    /// The sizes of all sets, one per set.
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.set.sizes()
+    }
+
+    /// The keys of every set, in the order the keys were added.
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<&K>> {
+        self.set
+            .components()
+            .into_iter()
+            .map(|component| component.into_iter().map(|i| &self.keys[i]).collect())
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_sets() {
+        let mut set = DisjointSet::new(6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 1));
+        assert!(!set.union(0, 2));
+        assert!(set.union(4, 5));
+
+        assert_eq!(set.count(), 3);
+        assert!(set.same(0, 2));
+        assert!(!set.same(0, 3));
+        assert_eq!(set.size(2), 3);
+        assert_eq!(set.size(3), 1);
+
+        let mut sizes: Vec<usize> = set.sizes().collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 2, 3]);
+        assert_eq!(set.components(), [vec![0, 1, 2], vec![3], vec![4, 5]]);
+
+        let i = set.add();
+        assert_eq!((i, set.count(), set.len()), (6, 4, 7));
+    }
+
+    #[test]
+    fn merges_long_chains() {
+        let mut set = DisjointSet::new(10_000);
+        for i in 1..set.len() {
+            set.union(i - 1, i);
+        }
+        assert_eq!(set.count(), 1);
+        assert_eq!(set.size(0), 10_000);
+        assert_eq!(set.find(9_999), set.root(0));
+    }
+
+    #[test]
+    fn merges_keys() {
+        let mut set = KeyedDisjointSet::new();
+        set.union("a", "b");
+        set.union("c", "d");
+        set.union("b", "d");
+        set.insert("e");
+
+        assert_eq!(set.count(), 2);
+        assert!(set.same(&"a", &"c"));
+        assert!(!set.same(&"a", &"e"));
+        assert!(!set.same(&"a", &"z"));
+        assert_eq!(set.size(&"a"), 4);
+        assert_eq!(set.size(&"z"), 0);
+        assert_eq!(set.find(&"z"), None);
+        assert_eq!(set.components(), [vec![&"a", &"b", &"c", &"d"], vec![&"e"]]);
+    }
+
+    #[test]
+    fn merges_grid_cells() {
+        let rows = ["AAB", "ABB", "CCB"];
+        let grid = Array2::from_shape_fn((3, 3), |(r, c)| rows[r].as_bytes()[c]);
+
+        let mut set = DisjointSet::from_grid(&grid);
+        assert_eq!(set.count(), 3);
+        assert!(set.same(0, 3));
+        assert_eq!(set.size(2), 4);
+        assert_eq!(set.size(6), 2);
+    }
+}
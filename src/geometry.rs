@@ -0,0 +1,300 @@
+//! Points, vectors and directions on a 2D grid.
+//!
+//! `x` is the column and `y` is the row, so `y` grows downwards and [`Direction::North`] is `(0, -1)`.
+//! Arithmetic is done on signed points, which are converted to grid indices with [`Point2::to_index`]:
+//! a point that is off the grid yields `None` instead of wrapping around.
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point or offset with signed coordinates, which is what most grid puzzles calculate with.
+pub type Vec2 = Point2<isize>;
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Taxicab distance, the number of orthogonal steps between two points.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Chessboard distance, the number of orthogonal or diagonal steps between two points.
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + TryInto<usize>,
+{
+    /// The `(row, col)` index of this point on a grid of `width` columns and `height` rows,
+    /// or `None` if the point is off the grid.
+    #[must_use]
+    pub fn to_index(self, width: usize, height: usize) -> Option<(usize, usize)> {
+        let col = self.x.try_into().ok()?;
+        let row = self.y.try_into().ok()?;
+        (col < width && row < height).then_some((row, col))
+    }
+
+    /// The index of this point in a row-major flat grid, or `None` if the point is off the grid.
+    #[must_use]
+    pub fn to_flat_index(self, width: usize, height: usize) -> Option<usize> {
+        self.to_index(width, height)
+            .map(|(row, col)| row * width + col)
+    }
+}
+
+impl Vec2 {
+    /// The point at a `(row, col)` grid index.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub const fn from_index((row, col): (usize, usize)) -> Self {
+        Self::new(col as isize, row as isize)
+    }
+
+    /// The orthogonally adjacent points, in the order of [`NEIGHBORS_4`].
+    pub fn neighbors_4(self) -> impl Iterator<Item = Self> {
+        NEIGHBORS_4.into_iter().map(move |delta| self + delta)
+    }
+
+    /// The orthogonally and diagonally adjacent points, in the order of [`NEIGHBORS_8`].
+    pub fn neighbors_8(self) -> impl Iterator<Item = Self> {
+        NEIGHBORS_8.into_iter().map(move |delta| self + delta)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(direction: Direction) -> Self {
+        direction.delta()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The four orthogonal directions, in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    #[must_use]
+    pub const fn delta(self) -> Vec2 {
+        match self {
+            Direction::North => Vec2::new(0, -1),
+            Direction::East => Vec2::new(1, 0),
+            Direction::South => Vec2::new(0, 1),
+            Direction::West => Vec2::new(-1, 0),
+        }
+    }
+
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Position in [`Direction::ALL`], for use as an array index.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Parse an arrow (`^>v<`) or a letter (`NESW`, `URDL`).
+    #[must_use]
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'N' | 'U' => Some(Direction::North),
+            '>' | 'E' | 'R' => Some(Direction::East),
+            'v' | 'S' | 'D' => Some(Direction::South),
+            '<' | 'W' | 'L' => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+/// Offsets to the orthogonal neighbors, clockwise from north.
+pub const NEIGHBORS_4: [Vec2; 4] = [
+    Direction::North.delta(),
+    Direction::East.delta(),
+    Direction::South.delta(),
+    Direction::West.delta(),
+];
+
+/// Offsets to the diagonal neighbors, clockwise from north-east.
+pub const DIAGONALS: [Vec2; 4] = [
+    Vec2::new(1, -1),
+    Vec2::new(1, 1),
+    Vec2::new(-1, 1),
+    Vec2::new(-1, -1),
+];
+
+/// Offsets to the orthogonal and diagonal neighbors, clockwise from north.
+pub const NEIGHBORS_8: [Vec2; 8] = [
+    Vec2::new(0, -1),
+    Vec2::new(1, -1),
+    Vec2::new(1, 0),
+    Vec2::new(1, 1),
+    Vec2::new(0, 1),
+    Vec2::new(-1, 1),
+    Vec2::new(-1, 0),
+    Vec2::new(-1, -1),
+];
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn does_arithmetic() {
+        let p = Vec2::new(3, 4);
+        assert_eq!(p + Vec2::new(1, -1), Vec2::new(4, 3));
+        assert_eq!(p - Vec2::new(5, 5), Vec2::new(-2, -1));
+        assert_eq!(p * 3, Vec2::new(9, 12));
+        assert_eq!(-p, Vec2::new(-3, -4));
+
+        let mut q = p;
+        q += Direction::East.into();
+        q -= Direction::North.into();
+        assert_eq!(q, Vec2::new(4, 5));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Vec2::new(1, 5);
+        let b = Vec2::new(4, 1);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Point2::<usize>::new(1, 5).manhattan(Point2::new(4, 1)), 7);
+    }
+
+    #[test]
+    fn converts_to_grid_indices() {
+        assert_eq!(Vec2::new(2, 1).to_index(3, 2), Some((1, 2)));
+        assert_eq!(Vec2::new(2, 1).to_flat_index(3, 2), Some(5));
+        assert_eq!(Vec2::new(-1, 0).to_index(3, 2), None);
+        assert_eq!(Vec2::new(3, 0).to_index(3, 2), None);
+        assert_eq!(Vec2::new(0, 2).to_index(3, 2), None);
+        assert_eq!(Vec2::from_index((1, 2)), Vec2::new(2, 1));
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.opposite().delta(), -dir.delta());
+        }
+        assert_eq!(Direction::from_char('^'), Some(Direction::North));
+        assert_eq!(Direction::from_char('x'), None);
+    }
+
+    #[test]
+    fn lists_neighbors() {
+        let p = Vec2::new(0, 0);
+        assert_eq!(p.neighbors_4().count(), 4);
+        let all: Vec<_> = p.neighbors_8().collect();
+        assert!(NEIGHBORS_4.iter().all(|n| all.contains(n)));
+        assert!(DIAGONALS.iter().all(|n| all.contains(n)));
+        assert!(all.iter().all(|n| n.chebyshev(p) == 1));
+    }
+}
@@ -1,3 +1,12 @@
+pub mod disjoint_set;
+pub mod geometry;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod range_set;
+pub mod regions;
+pub mod rng;
+pub mod search;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
@@ -1,4 +1,7 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, leaderboard, progress, read, scaffold, solve, time, validate,
+};
+use advent_of_code::template::profile;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,15 +10,22 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{bench_config::BenchConfig, Day};
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            overwrite: bool,
+            profile: Option<String>,
         },
         Read {
             day: Day,
+            part: Option<u8>,
+            code: bool,
+        },
+        Validate {
+            day: Day,
         },
         Scaffold {
             day: Day,
@@ -26,42 +36,93 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            allocs: bool,
             submit: Option<u8>,
+            profile: Option<String>,
+            all_profiles: bool,
+            solution_args: Vec<String>,
         },
         All {
             release: bool,
+            allocs: bool,
+            profile: Option<String>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            allocs: bool,
+            bench_config: BenchConfig,
+            profile: Option<String>,
+        },
+        Progress {
+            store: bool,
+        },
+        Leaderboard {
+            file: Option<String>,
+            id: Option<u64>,
+            day: Option<Day>,
+            refresh: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        // arguments after `--` are passed on to the solution, e.g. `cargo solve 11 -- --blinks 100`.
+        let mut raw_args: Vec<String> = std::env::args().skip(1).collect();
+        let mut solution_args = match raw_args.iter().position(|arg| arg == "--") {
+            Some(i) => raw_args.split_off(i).split_off(1),
+            None => vec![],
+        };
+        let mut args =
+            pico_args::Arguments::from_vec(raw_args.into_iter().map(Into::into).collect());
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                allocs: args.contains("--allocs"),
+                profile: args.opt_value_from_str("--profile")?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let allocs = args.contains("--allocs");
+                let profile = args.opt_value_from_str("--profile")?;
+
+                let mut bench_config = BenchConfig::from_env()?;
+                bench_config.apply_args(&mut args)?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    allocs,
+                    bench_config,
+                    profile,
                 }
             }
+            Some("progress") => AppArguments::Progress {
+                store: args.contains("--store"),
+            },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                file: args.opt_value_from_str("--file")?,
+                id: args.opt_value_from_str("--id")?,
+                day: args.opt_value_from_str("--day")?,
+                refresh: args.contains("--refresh"),
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                overwrite: args.contains("--overwrite"),
+                profile: args.opt_value_from_str("--profile")?,
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?,
+                code: args.contains("--code"),
+            },
+            Some("validate") => AppArguments::Validate {
+                day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
@@ -73,6 +134,10 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                allocs: args.contains("--allocs"),
+                profile: args.opt_value_from_str("--profile")?,
+                all_profiles: args.contains("--all-profiles"),
+                solution_args: std::mem::take(&mut solution_args),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -90,11 +155,24 @@ mod args {
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
+        if !solution_args.is_empty() {
+            eprintln!("Warning: only `solve` passes arguments to the solution, ignoring {solution_args:?}.");
+        }
 
         Ok(app_args)
     }
 }
 
+/// Select the input profile for the command and the solutions it runs.
+fn select_profile(profile: Option<String>) {
+    if let Some(profile) = profile {
+        if let Err(e) = profile::set_profile(&profile) {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
 fn main() {
     match parse() {
         Err(err) => {
@@ -102,10 +180,42 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                release,
+                allocs,
+                profile,
+            } => {
+                select_profile(profile);
+                all::handle(release, allocs);
+            }
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                allocs,
+                bench_config,
+                profile,
+            } => {
+                select_profile(profile);
+                time::handle(day, all, store, allocs, &bench_config);
+            }
+            AppArguments::Progress { store } => progress::handle(store),
+            AppArguments::Leaderboard {
+                file,
+                id,
+                day,
+                refresh,
+            } => leaderboard::handle(file, id, day, refresh),
+            AppArguments::Download {
+                day,
+                overwrite,
+                profile,
+            } => {
+                select_profile(profile);
+                download::handle(day, overwrite);
+            }
+            AppArguments::Read { day, part, code } => read::handle(day, part, code),
+            AppArguments::Validate { day } => validate::handle(day),
             AppArguments::Scaffold {
                 day,
                 download,
@@ -113,22 +223,52 @@ fn main() {
             } => {
                 scaffold::handle(day, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(day, false);
                 }
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                allocs,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                profile,
+                all_profiles,
+                solution_args,
+            } => {
+                if all_profiles {
+                    let conflicts: Vec<&str> = [
+                        ("`--profile`", profile.is_some()),
+                        ("`--dhat`", dhat),
+                        ("`--allocs`", allocs),
+                        ("`--submit`", submit.is_some()),
+                        ("arguments after `--`", !solution_args.is_empty()),
+                    ]
+                    .into_iter()
+                    .filter_map(|(flag, is_set)| is_set.then_some(flag))
+                    .collect();
+
+                    if !conflicts.is_empty() {
+                        eprintln!(
+                            "Error: `--all-profiles` cannot be combined with {}.",
+                            conflicts.join(", ")
+                        );
+                        std::process::exit(1);
+                    }
+
+                    solve::handle_profiles(day, release);
+                } else {
+                    select_profile(profile);
+                    solve::handle(day, release, dhat, allocs, submit, &solution_args);
+                }
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
                         scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        download::handle(day, false);
+                        read::handle(day, None, false)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,368 @@
+//! Integer math: decimal digits, divisibility, modular arithmetic and binomials.
+//!
+//! Everything is exact, unlike going through `f64` (e.g. `(n as f64).log10()` is wrong for 0 and for values
+//! above 2^53). Operations that can overflow return `None` instead of wrapping.
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// Primitive integers that the generic helpers work with.
+pub trait Integer:
+    Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TEN: Self;
+
+    /// The absolute value. Identity for unsigned integers.
+    #[must_use]
+    fn abs_value(self) -> Self;
+
+    /// The number of decimal digits of the absolute value.
+    #[must_use]
+    fn decimal_digits(self) -> u32;
+
+    fn checked_add_value(self, rhs: Self) -> Option<Self>;
+    fn checked_mul_value(self, rhs: Self) -> Option<Self>;
+    fn checked_pow_value(self, exp: u32) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    (unsigned: $($t:ty),*) => {
+        $(impl Integer for $t {
+            impl_integer!(@common $t);
+
+            fn abs_value(self) -> Self {
+                self
+            }
+
+            fn decimal_digits(self) -> u32 {
+                self.checked_ilog10().map_or(1, |d| d + 1)
+            }
+        })*
+    };
+    (signed: $($t:ty),*) => {
+        $(impl Integer for $t {
+            impl_integer!(@common $t);
+
+            fn abs_value(self) -> Self {
+                self.abs()
+            }
+
+            fn decimal_digits(self) -> u32 {
+                self.unsigned_abs().checked_ilog10().map_or(1, |d| d + 1)
+            }
+        })*
+    };
+    (@common $t:ty) => {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+        const TEN: Self = 10;
+
+        fn checked_add_value(self, rhs: Self) -> Option<Self> {
+            self.checked_add(rhs)
+        }
+
+        fn checked_mul_value(self, rhs: Self) -> Option<Self> {
+            self.checked_mul(rhs)
+        }
+
+        fn checked_pow_value(self, exp: u32) -> Option<Self> {
+            self.checked_pow(exp)
+        }
+    };
+}
+
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_integer!(signed: i8, i16, i32, i64, i128, isize);
+
+/* -------------------------------------------------------------------------- */
+
+/// The number of decimal digits of `n`, ignoring the sign. 0 has one digit.
+#[must_use]
+pub fn num_digits<T: Integer>(n: T) -> u32 {
+    n.decimal_digits()
+}
+
+/// `10^exp`, or `None` if it does not fit.
+#[must_use]
+pub fn pow10<T: Integer>(exp: u32) -> Option<T> {
+    T::TEN.checked_pow_value(exp)
+}
+
+/// Split off the last `at` digits of `n`, e.g. `split_digits(1234, 1) == (123, 4)`.
+#[must_use]
+pub fn split_digits<T: Integer>(n: T, at: u32) -> (T, T) {
+    match pow10::<T>(at) {
+        Some(divisor) => (n / divisor, n % divisor),
+        // all digits are split off.
+        None => (T::ZERO, n),
+    }
+}
+
+/// The digits of `a` followed by the digits of `b`, e.g. `concat(12, 345) == Some(12345)`.
+/// `b` must not be negative. Returns `None` on overflow.
+#[must_use]
+pub fn concat<T: Integer>(a: T, b: T) -> Option<T> {
+    a.checked_mul_value(pow10(num_digits(b))?)?
+        .checked_add_value(b)
+}
+
+/// Greatest common divisor, which is never negative. `gcd(0, 0) == 0`.
+#[must_use]
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs_value(), b.abs_value());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, which is never negative. Returns `None` on overflow.
+#[must_use]
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a.abs_value() / gcd(a, b)).checked_mul_value(b.abs_value())
+}
+
+/// Extended Euclidean algorithm: `(g, x, y)` with `a * x + b * y == g`, where `g` is the gcd of `a` and `b`.
+#[must_use]
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `base^exp mod modulus`.
+#[must_use]
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let result = result as u64;
+    result
+}
+
+/// The inverse of `a` modulo `modulus` in `0..modulus`, or `None` if they are not coprime.
+#[must_use]
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = ext_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Chinese remainder theorem: the smallest `x >= 0` with `x ≡ r (mod m)` for every `(r, m)`, along with the
+/// combined modulus. Moduli do not need to be coprime. Returns `None` if the congruences contradict each other
+/// or the combined modulus overflows.
+#[must_use]
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i64 = 0;
+    let mut modulus: i64 = 1;
+
+    for &(r, m) in congruences {
+        let r = r.rem_euclid(m);
+        // solve x + modulus * k ≡ r (mod m) for k.
+        let (g, p, _) = ext_gcd(modulus, m);
+        let diff = r - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = m / g;
+        let k = (i128::from(diff / g) * i128::from(p)).rem_euclid(i128::from(step));
+        let next_modulus = modulus.checked_mul(step)?;
+        let next_x = (i128::from(x) + i128::from(modulus) * k).rem_euclid(i128::from(next_modulus));
+
+        modulus = next_modulus;
+        x = i64::try_from(next_x).ok()?;
+    }
+
+    Some((x, modulus))
+}
+
+/// The number of ways to choose `k` of `n` items. Returns `None` on overflow.
+#[must_use]
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        // exact: the product of i + 1 consecutive numbers is divisible by (i + 1)!.
+        result = result.checked_mul(u128::from(n - i))? / u128::from(i + 1);
+    }
+
+    u64::try_from(result).ok()
+}
+
+/// Binomials `C(n, k)` modulo a prime, from precomputed factorials up to `n`.
+#[derive(Clone, Debug)]
+pub struct Binomials {
+    modulus: u64,
+    factorials: Vec<u64>,
+    inverse_factorials: Vec<u64>,
+}
+
+impl Binomials {
+    /// Precompute factorials up to `max_n`. `modulus` must be a prime larger than `max_n`.
+    #[must_use]
+    pub fn new(max_n: usize, modulus: u64) -> Self {
+        let mut factorials = vec![1; max_n + 1];
+        for i in 1..=max_n {
+            factorials[i] = mul_mod(factorials[i - 1], i as u64, modulus);
+        }
+
+        // Fermat's little theorem: a^(p - 2) is the inverse of a modulo a prime p.
+        let mut inverse_factorials = vec![1; max_n + 1];
+        inverse_factorials[max_n] = mod_pow(factorials[max_n], modulus - 2, modulus);
+        for i in (1..=max_n).rev() {
+            inverse_factorials[i - 1] = mul_mod(inverse_factorials[i], i as u64, modulus);
+        }
+
+        Self {
+            modulus,
+            factorials,
+            inverse_factorials,
+        }
+    }
+
+    /// `C(n, k) mod modulus`. `n` must not exceed the precomputed maximum.
+    #[must_use]
+    pub fn get(&self, n: usize, k: usize) -> u64 {
+        if k > n {
+            return 0;
+        }
+        let m = self.modulus;
+        mul_mod(
+            mul_mod(self.factorials[n], self.inverse_factorials[k], m),
+            self.inverse_factorials[n - k],
+            m,
+        )
+    }
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    #[allow(clippy::cast_possible_truncation)]
+    let result = (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64;
+    result
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_digits() {
+        assert_eq!(num_digits(0u32), 1);
+        assert_eq!(num_digits(9u8), 1);
+        assert_eq!(num_digits(10usize), 2);
+        assert_eq!(num_digits(-123i32), 3);
+        assert_eq!(num_digits(999_999_999_999_999_999u64), 18);
+        assert_eq!(num_digits(1_000_000_000_000_000_000u64), 19);
+        assert_eq!(num_digits(u64::MAX), 20);
+        assert_eq!(num_digits(i64::MIN), 19);
+    }
+
+    #[test]
+    fn splits_and_concatenates() {
+        assert_eq!(split_digits(1234, 2), (12, 34));
+        assert_eq!(split_digits(1000, 2), (10, 0));
+        assert_eq!(split_digits(5u8, 3), (0, 5));
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(15usize, 0), Some(150));
+        assert_eq!(concat(0, 7), Some(7));
+        assert_eq!(concat(u64::MAX / 10, 9), None);
+        assert_eq!(concat(255u8, 1), None);
+    }
+
+    #[test]
+    fn finds_divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-4, 6), 2);
+        assert_eq!(gcd(4, -6), 2);
+        assert_eq!(gcd(0, 5u32), 5);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(-4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, 2), None);
+
+        let (g, x, y) = ext_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        let (g, x, y) = ext_gcd(-240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(-240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn does_modular_arithmetic() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 7), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli that are not coprime.
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn counts_combinations() {
+        assert_eq!(binomial(5, 2), Some(10));
+        assert_eq!(binomial(5, 0), Some(1));
+        assert_eq!(binomial(3, 5), Some(0));
+        assert_eq!(binomial(62, 31), Some(465_428_353_255_261_088));
+        assert_eq!(binomial(100, 50), None);
+
+        let binomials = Binomials::new(100, 1_000_000_007);
+        assert_eq!(binomials.get(5, 2), 10);
+        assert_eq!(binomials.get(100, 50), 538_992_043);
+        assert_eq!(binomials.get(3, 5), 0);
+    }
+}
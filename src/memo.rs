@@ -0,0 +1,164 @@
+//! Memoization that is scoped to a call, and a registry for global caches that the runner resets.
+//!
+//! A global cache, such as the one `#[cached]` creates, outlives the call that filled it: benchmark runs after the
+//! first one only measure cache lookups, and tests share state with each other. A [`Memo`] is created by the
+//! solution and passed to the memoized function explicitly, so it is dropped with the result:
+//!
+//! ```ignore
+//! fn count(memo: &mut Memo<(u64, usize), usize>, stone: u64, depth: usize) -> usize {
+//!     memo.get_or_compute((stone, depth), |memo| /* recursive calls use `memo` */)
+//! }
+//! ```
+//!
+//! Global caches that are kept can be registered with [`register_reset`], usually through the `reset` option of the
+//! `solution!` macro. The runner clears them before every part and, with `cargo time --reset-caches`, before every
+//! benchmark sample.
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+
+/// A cache of the results of a function, owned by the caller.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cached value for `key`, or the value computed by `compute`, which can recurse through the memo it receives.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    /// Remove all values and reset the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// The number of cached values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Lookups that were answered from the cache.
+    #[must_use]
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Lookups that had to compute the value.
+    #[must_use]
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+type Reset = Box<dyn Fn() + Send>;
+
+static GLOBAL_CACHES: Mutex<Vec<Reset>> = Mutex::new(vec![]);
+
+/// Register a function that clears a global cache, e.g. `|| COMPUTE.lock().unwrap().cache_clear()` for a function
+/// annotated with `#[cached]`.
+pub fn register_reset(reset: impl Fn() + Send + 'static) {
+    if let Ok(mut caches) = GLOBAL_CACHES.lock() {
+        caches.push(Box::new(reset));
+    }
+}
+
+/// Whether any global caches were registered.
+#[must_use]
+pub fn has_global_caches() -> bool {
+    GLOBAL_CACHES.lock().is_ok_and(|caches| !caches.is_empty())
+}
+
+/// Clear all registered global caches.
+pub fn reset_global_caches() {
+    if let Ok(caches) = GLOBAL_CACHES.lock() {
+        caches.iter().for_each(|reset| reset());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_compute(n, |memo| fib(memo, n - 1) + fib(memo, n - 2))
+    }
+
+    #[test]
+    fn memoizes_recursive_calls() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 89);
+        assert_eq!(memo.misses(), 89);
+        assert_eq!(memo.hits(), 87);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.get(&90), None);
+    }
+
+    #[test]
+    fn resets_global_caches() {
+        static RESETS: AtomicUsize = AtomicUsize::new(0);
+
+        register_reset(|| {
+            RESETS.fetch_add(1, Ordering::SeqCst);
+        });
+        assert!(has_global_caches());
+
+        reset_global_caches();
+        assert!(RESETS.load(Ordering::SeqCst) >= 1);
+    }
+}
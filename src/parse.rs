@@ -0,0 +1,322 @@
+//! Helpers for parsing puzzle inputs.
+//!
+//! Unlike `filter_map(|s| s.parse().ok())`, malformed tokens are not skipped: every helper returns a [`ParseError`]
+//! that points at the line and column of the offending token.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A parse error with the 1-based line and column it occurred at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// A line of the input, along with its 1-based line number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// Iterate the lines of `input` with their line numbers.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+/// Split `input` into sections separated by one or more blank lines.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![];
+    let mut current = vec![];
+
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            if !current.is_empty() {
+                sections.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+
+    if !current.is_empty() {
+        sections.push(current);
+    }
+
+    sections
+}
+
+/// Like [`sections`], but fails unless there are exactly `N` sections.
+pub fn sections_exact<const N: usize>(input: &str) -> ParseResult<[Vec<Line<'_>>; N]> {
+    let sections = sections(input);
+
+    sections.try_into().map_err(|sections: Vec<Vec<Line>>| {
+        let line = sections
+            .last()
+            .and_then(|section| section.last())
+            .map_or(1, |line| line.number + 1);
+
+        ParseError {
+            line,
+            column: 1,
+            message: format!(
+                "expected {N} sections separated by blank lines, found {}",
+                sections.len()
+            ),
+        }
+    })
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at `column` of this line.
+    pub fn error(&self, column: usize, message: impl Display) -> ParseError {
+        ParseError {
+            line: self.number,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    /// Column of `token`, which must be a slice of this line.
+    fn column_of(&self, token: &str) -> usize {
+        token.as_ptr() as usize - self.text.as_ptr() as usize + 1
+    }
+
+    fn end_column(&self) -> usize {
+        self.text.len() + 1
+    }
+
+    /// Parse `token`, which must be a slice of this line.
+    pub fn parse_token<T>(&self, token: &'a str) -> ParseResult<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        token.parse().map_err(|e| {
+            self.error(
+                self.column_of(token),
+                format_args!("invalid value `{token}`: {e}"),
+            )
+        })
+    }
+
+    /// Extract all signed integers from the line, ignoring any text around them.
+    pub fn ints<T>(&self) -> ParseResult<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut ints = vec![];
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let is_sign = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+
+            if is_sign || bytes[i].is_ascii_digit() {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                ints.push(self.parse_token(&self.text[start..i])?);
+            } else {
+                i += 1;
+            }
+        }
+
+        Ok(ints)
+    }
+
+    /// Parse all whitespace-separated fields of the line.
+    pub fn words<T>(&self) -> ParseResult<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .split_ascii_whitespace()
+            .map(|token| self.parse_token(token))
+            .collect()
+    }
+
+    /// Parse all fields of the line separated by `separator`.
+    pub fn split<T>(&self, separator: &str) -> ParseResult<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .split(separator)
+            .map(|token| self.parse_token(token))
+            .collect()
+    }
+
+    /// Parse a `key<separator> values` line, where values are separated by whitespace.
+    pub fn key_values<K, V>(&self, separator: &str) -> ParseResult<(K, Vec<V>)>
+    where
+        K: FromStr,
+        K::Err: Display,
+        V: FromStr,
+        V::Err: Display,
+    {
+        let (key, values) = self.text.split_once(separator).ok_or_else(|| {
+            self.error(
+                self.end_column(),
+                format_args!("expected `<key>{separator} <values>`"),
+            )
+        })?;
+
+        let key = self.parse_token(key.trim())?;
+        let values = values
+            .split_ascii_whitespace()
+            .map(|token| self.parse_token(token))
+            .collect::<ParseResult<Vec<V>>>()?;
+
+        if values.is_empty() {
+            return Err(self.error(self.end_column(), "expected at least one value"));
+        }
+
+        Ok((key, values))
+    }
+
+    /// Parse the line into a tuple of fields separated by `separator`, e.g. `line.tuple::<(u32, u32)>("|")`.
+    pub fn tuple<T: FromFields>(&self, separator: &str) -> ParseResult<T> {
+        T::from_fields(self, &mut self.text.split(separator))
+    }
+
+    /// Parse the line into a tuple of whitespace-separated fields, e.g. `line.tuple_words::<(u32, u32)>()`.
+    pub fn tuple_words<T: FromFields>(&self) -> ParseResult<T> {
+        T::from_fields(self, &mut self.text.split_ascii_whitespace())
+    }
+}
+
+/// Types that can be parsed from a fixed number of fields, implemented for tuples of up to 4 elements.
+pub trait FromFields: Sized {
+    fn from_fields<'a>(
+        line: &Line<'a>,
+        fields: &mut dyn Iterator<Item = &'a str>,
+    ) -> ParseResult<Self>;
+}
+
+macro_rules! impl_from_fields {
+    ($len:literal; $($t:ident),+) => {
+        impl<$($t),+> FromFields for ($($t,)+)
+        where
+            $($t: FromStr, $t::Err: Display),+
+        {
+            fn from_fields<'a>(
+                line: &Line<'a>,
+                fields: &mut dyn Iterator<Item = &'a str>,
+            ) -> ParseResult<Self> {
+                let tuple = ($(
+                    match fields.next() {
+                        Some(token) => line.parse_token::<$t>(token)?,
+                        None => {
+                            return Err(line.error(
+                                line.end_column(),
+                                concat!("expected ", $len, " fields"),
+                            ))
+                        }
+                    },
+                )+);
+
+                if let Some(extra) = fields.next() {
+                    return Err(line.error(
+                        line.column_of(extra),
+                        concat!("expected ", $len, " fields, found more"),
+                    ));
+                }
+
+                Ok(tuple)
+            }
+        }
+    };
+}
+
+impl_from_fields!(1; A);
+impl_from_fields!(2; A, B);
+impl_from_fields!(3; A, B, C);
+impl_from_fields!(4; A, B, C, D);
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line<'_> {
+        Line { number: 3, text }
+    }
+
+    #[test]
+    fn extracts_signed_ints() {
+        let ints: Vec<i64> = line("p=0,-4 v=3-3 x").ints().unwrap();
+        assert_eq!(ints, vec![0, -4, 3, -3]);
+
+        let err = line("a 300").ints::<u8>().unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+    }
+
+    #[test]
+    fn reports_column_of_bad_token() {
+        let err = line("3   4x").words::<u32>().unwrap_err();
+        assert_eq!((err.line, err.column), (3, 5));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 5: invalid value `4x`: invalid digit found in string"
+        );
+
+        let err = line("1,,2").split::<u32>(",").unwrap_err();
+        assert_eq!(err.column, 3);
+    }
+
+    #[test]
+    fn parses_key_values() {
+        let (key, values): (u64, Vec<u64>) = line("190: 10 19").key_values(":").unwrap();
+        assert_eq!((key, values), (190, vec![10, 19]));
+
+        let err = line("3267 81").key_values::<u64, u64>(":").unwrap_err();
+        assert_eq!(err.column, 8);
+        assert!(line("190:").key_values::<u64, u64>(":").is_err());
+    }
+
+    #[test]
+    fn parses_tuples() {
+        assert_eq!(line("47|53").tuple::<(u32, u32)>("|"), Ok((47, 53)));
+        assert_eq!(line("3   4").tuple_words::<(u32, u32)>(), Ok((3, 4)));
+
+        let err = line("47|53|1").tuple::<(u32, u32)>("|").unwrap_err();
+        assert_eq!(err.column, 7);
+        let err = line("47").tuple::<(u32, u32)>("|").unwrap_err();
+        assert_eq!(err.message, "expected 2 fields");
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "1|2\n3|4\n\n\n1,2\n";
+        let [rules, updates] = sections_exact::<2>(input).unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(updates[0].number, 5);
+
+        let err = sections_exact::<3>(input).unwrap_err();
+        assert_eq!(err.line, 6);
+    }
+}
@@ -0,0 +1,675 @@
+//! A set of integers stored as disjoint half-open ranges, e.g. the free spans of a disk.
+//!
+//! Ranges are kept merged: overlapping and adjacent ranges are combined on insert, so `0..3` and `3..5` become
+//! `0..5`. The ranges live in a treap (a randomized balanced binary tree) where every node also tracks the longest
+//! range and the total length of its subtree, so [`RangeSet::first_fit`] and coverage queries run in O(log n)
+//! along with inserts and removals. [`RangeSet::split_off`] is the exception, it copies the smaller half.
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::math::Integer;
+use crate::rng::XorShift;
+
+const NIL: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    start: T,
+    end: T,
+    priority: u64,
+    left: usize,
+    right: usize,
+    /// Length of the longest range in this subtree.
+    max_len: T,
+    /// Total length of the ranges in this subtree.
+    total: T,
+    /// Number of nodes in this subtree.
+    count: usize,
+}
+
+#[derive(Clone)]
+pub struct RangeSet<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    root: usize,
+    len: usize,
+    /// Draws the priorities of new nodes.
+    rng: XorShift,
+}
+
+impl<T: Integer> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer> RangeSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![],
+            free: vec![],
+            root: NIL,
+            len: 0,
+            rng: XorShift::new(0x9E37_79B9_7F4A_7C15),
+        }
+    }
+
+    /// The number of disjoint ranges.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The ranges in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        let mut stack = vec![];
+        let mut cur = self.root;
+
+        std::iter::from_fn(move || {
+            while cur != NIL {
+                stack.push(cur);
+                cur = self.nodes[cur].left;
+            }
+            let i = stack.pop()?;
+            cur = self.nodes[i].right;
+            Some(self.nodes[i].start..self.nodes[i].end)
+        })
+    }
+
+    /// Whether `value` is in one of the ranges.
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        self.range_containing(value).is_some()
+    }
+
+    /// The range that `value` is in.
+    #[must_use]
+    pub fn range_containing(&self, value: T) -> Option<Range<T>> {
+        let mut cur = self.root;
+        while cur != NIL {
+            let node = &self.nodes[cur];
+            if value < node.start {
+                cur = node.left;
+            } else if value >= node.end {
+                cur = node.right;
+            } else {
+                return Some(node.start..node.end);
+            }
+        }
+        None
+    }
+
+    /// The leftmost range that is at least `len` long.
+    #[must_use]
+    pub fn first_fit(&self, len: T) -> Option<Range<T>> {
+        let mut cur = self.root;
+        while cur != NIL && self.max_len(cur) >= len {
+            let node = &self.nodes[cur];
+            if self.max_len(node.left) >= len {
+                cur = node.left;
+            } else if node.end - node.start >= len {
+                return Some(node.start..node.end);
+            } else {
+                cur = node.right;
+            }
+        }
+        None
+    }
+
+    /// The total length of all ranges.
+    #[must_use]
+    pub fn coverage(&self) -> T {
+        self.total(self.root)
+    }
+
+    /// The number of values in `range` that are in the set.
+    #[must_use]
+    pub fn coverage_of(&self, range: Range<T>) -> T {
+        if range.start >= range.end {
+            return T::ZERO;
+        }
+        self.coverage_before(range.end) - self.coverage_before(range.start)
+    }
+
+    /// The number of values below `x` that are in the set.
+    fn coverage_before(&self, x: T) -> T {
+        let mut covered = T::ZERO;
+        let mut cur = self.root;
+        while cur != NIL {
+            let node = &self.nodes[cur];
+            if x <= node.start {
+                cur = node.left;
+            } else {
+                covered = covered + self.total(node.left) + node.end.min(x) - node.start;
+                if x <= node.end {
+                    break;
+                }
+                cur = node.right;
+            }
+        }
+        covered
+    }
+
+    /// Add all values of `range`, merging it with overlapping and adjacent ranges.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        let (left, rest) = self.split(self.root, range.start, false);
+        let (middle, right) = self.split(rest, range.end, true);
+        let (mut start, mut end) = (range.start, range.end);
+
+        // the range before may reach into the new range.
+        let (left, last) = self.pop_last(left);
+        let left = match last {
+            Some(i) if self.nodes[i].end >= start => {
+                start = self.nodes[i].start;
+                end = end.max(self.nodes[i].end);
+                self.release(i);
+                left
+            }
+            Some(i) => self.merge(left, i),
+            None => left,
+        };
+
+        // ranges that start inside the new range are absorbed, the last one may reach beyond it.
+        if middle != NIL {
+            end = end.max(self.nodes[self.last(middle)].end);
+            self.release_tree(middle);
+        }
+
+        let node = self.alloc(start, end);
+        let left = self.merge(left, node);
+        self.root = self.merge(left, right);
+    }
+
+    /// Remove all values of `range`, splitting ranges that extend beyond it.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        let (left, rest) = self.split(self.root, range.start, false);
+        let (middle, right) = self.split(rest, range.end, false);
+        let mut after = None;
+
+        // the range before may reach into or across the removed range.
+        let (mut left, last) = self.pop_last(left);
+        if let Some(i) = last {
+            let end = self.nodes[i].end;
+            if end > range.start {
+                self.nodes[i].end = range.start;
+                self.update(i);
+            }
+            if end > range.end {
+                after = Some(end);
+            }
+            left = self.merge(left, i);
+        }
+
+        if middle != NIL {
+            let end = self.nodes[self.last(middle)].end;
+            if end > range.end {
+                after = Some(end);
+            }
+            self.release_tree(middle);
+        }
+
+        let right = match after {
+            Some(end) => {
+                let node = self.alloc(range.end, end);
+                self.merge(node, right)
+            }
+            None => right,
+        };
+        self.root = self.merge(left, right);
+    }
+
+    /// Add all values of `other`.
+    pub fn merge_with(&mut self, other: &RangeSet<T>) {
+        for range in other.iter() {
+            self.insert(range);
+        }
+    }
+
+    /// The values in either set.
+    #[must_use]
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut union = self.clone();
+        union.merge_with(other);
+        union
+    }
+
+    /// The values in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut intersection = RangeSet::new();
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let start = x.start.max(y.start);
+            let end = x.end.min(y.end);
+            if start < end {
+                intersection.insert(start..end);
+            }
+
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        intersection
+    }
+
+    /// Split off the values from `at` onwards into a new set. A range across `at` is cut in two.
+    ///
+    /// Runs in O(log n + min(k, n - k)) for a split into k and n - k ranges: the tree is split in O(log n), then the
+    /// half with fewer ranges is copied into a new arena while the other one keeps the current arena.
+    pub fn split_off(&mut self, at: T) -> RangeSet<T> {
+        let (left, mut right) = self.split(self.root, at, false);
+
+        let (mut left, last) = self.pop_last(left);
+        if let Some(i) = last {
+            let end = self.nodes[i].end;
+            if end > at {
+                self.nodes[i].end = at;
+                self.update(i);
+                let node = self.alloc(at, end);
+                right = self.merge(node, right);
+            }
+            left = self.merge(left, i);
+        }
+
+        let mut upper = RangeSet::new();
+        if self.count(right) <= self.count(left) {
+            upper.root = upper.adopt(self, right);
+            self.root = left;
+        } else {
+            std::mem::swap(self, &mut upper);
+            self.root = self.adopt(&mut upper, left);
+            upper.root = right;
+        }
+
+        upper
+    }
+
+    /* ---------------------------------- tree ---------------------------------- */
+
+    fn max_len(&self, i: usize) -> T {
+        if i == NIL {
+            T::ZERO
+        } else {
+            self.nodes[i].max_len
+        }
+    }
+
+    fn total(&self, i: usize) -> T {
+        if i == NIL {
+            T::ZERO
+        } else {
+            self.nodes[i].total
+        }
+    }
+
+    fn count(&self, i: usize) -> usize {
+        if i == NIL {
+            0
+        } else {
+            self.nodes[i].count
+        }
+    }
+
+    fn update(&mut self, i: usize) {
+        let (left, right) = (self.nodes[i].left, self.nodes[i].right);
+        let len = self.nodes[i].end - self.nodes[i].start;
+        let max_len = len.max(self.max_len(left)).max(self.max_len(right));
+        let total = len + self.total(left) + self.total(right);
+        let count = 1 + self.count(left) + self.count(right);
+        self.nodes[i].max_len = max_len;
+        self.nodes[i].total = total;
+        self.nodes[i].count = count;
+    }
+
+    fn alloc(&mut self, start: T, end: T) -> usize {
+        let node = Node {
+            start,
+            end,
+            priority: self.rng.next_u64(),
+            left: NIL,
+            right: NIL,
+            max_len: end - start,
+            total: end - start,
+            count: 1,
+        };
+        self.push(node)
+    }
+
+    fn push(&mut self, node: Node<T>) -> usize {
+        self.len += 1;
+        match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn release(&mut self, i: usize) {
+        self.len -= 1;
+        self.free.push(i);
+    }
+
+    /// Move a tree of `other` into this arena as it is. Returns the new index of its root.
+    fn adopt(&mut self, other: &mut RangeSet<T>, i: usize) -> usize {
+        if i == NIL {
+            return NIL;
+        }
+
+        let mut node = other.nodes[i].clone();
+        node.left = self.adopt(other, node.left);
+        node.right = self.adopt(other, node.right);
+        other.release(i);
+        self.push(node)
+    }
+
+    fn release_tree(&mut self, i: usize) {
+        if i != NIL {
+            self.release_tree(self.nodes[i].left);
+            self.release_tree(self.nodes[i].right);
+            self.release(i);
+        }
+    }
+
+    /// Split a tree into the nodes that start before `key` (or at `key` if `inclusive`) and the rest.
+    fn split(&mut self, i: usize, key: T, inclusive: bool) -> (usize, usize) {
+        if i == NIL {
+            return (NIL, NIL);
+        }
+
+        let start = self.nodes[i].start;
+        if start < key || (inclusive && start == key) {
+            let (left, right) = self.split(self.nodes[i].right, key, inclusive);
+            self.nodes[i].right = left;
+            self.update(i);
+            (i, right)
+        } else {
+            let (left, right) = self.split(self.nodes[i].left, key, inclusive);
+            self.nodes[i].left = right;
+            self.update(i);
+            (left, i)
+        }
+    }
+
+    /// Join two trees, where all nodes of `a` come before all nodes of `b`.
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+
+        if self.nodes[a].priority > self.nodes[b].priority {
+            let right = self.merge(self.nodes[a].right, b);
+            self.nodes[a].right = right;
+            self.update(a);
+            a
+        } else {
+            let left = self.merge(a, self.nodes[b].left);
+            self.nodes[b].left = left;
+            self.update(b);
+            b
+        }
+    }
+
+    fn last(&self, mut i: usize) -> usize {
+        while self.nodes[i].right != NIL {
+            i = self.nodes[i].right;
+        }
+        i
+    }
+
+    /// Detach the last node of a tree. Returns the remaining tree and the detached node.
+    fn pop_last(&mut self, i: usize) -> (usize, Option<usize>) {
+        if i == NIL {
+            return (NIL, None);
+        }
+
+        if self.nodes[i].right == NIL {
+            let left = self.nodes[i].left;
+            self.nodes[i].left = NIL;
+            self.update(i);
+            return (left, Some(i));
+        }
+
+        let (right, last) = self.pop_last(self.nodes[i].right);
+        self.nodes[i].right = right;
+        self.update(i);
+        (i, last)
+    }
+}
+
+impl<T: Integer> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Integer> PartialEq for RangeSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Integer> Eq for RangeSet<T> {}
+
+impl<T: Integer + Debug> Debug for RangeSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_ranges() {
+        let mut set: RangeSet<u32> = [0..3, 5..7, 3..4].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..4, 5..7]);
+        set.insert(4..5);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..7]);
+        set.insert(10..12);
+        set.insert(2..11);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..12]);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn removes_ranges() {
+        let mut set: RangeSet<i64> = [0..10, 20..30].into_iter().collect();
+        set.remove(3..5);
+        set.remove(8..22);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..3, 5..8, 22..30]);
+        assert_eq!(set.coverage(), 14);
+        assert_eq!(set.coverage_of(2..25), 7);
+        assert!(set.contains(7) && !set.contains(8));
+        assert_eq!(set.range_containing(25), Some(22..30));
+    }
+
+    #[test]
+    fn finds_first_fit() {
+        let set: RangeSet<usize> = [0..1, 2..5, 6..8, 10..14].into_iter().collect();
+        assert_eq!(set.first_fit(1), Some(0..1));
+        assert_eq!(set.first_fit(2), Some(2..5));
+        assert_eq!(set.first_fit(4), Some(10..14));
+        assert_eq!(set.first_fit(5), None);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a: RangeSet<u32> = [0..5, 10..15].into_iter().collect();
+        let b: RangeSet<u32> = [3..12].into_iter().collect();
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), [0..15]);
+        assert_eq!(
+            a.intersection(&b).iter().collect::<Vec<_>>(),
+            [3..5, 10..12]
+        );
+
+        let mut a = a;
+        let upper = a.split_off(12);
+        assert_eq!(a.iter().collect::<Vec<_>>(), [0..5, 10..12]);
+        assert_eq!(upper.iter().collect::<Vec<_>>(), [12..15]);
+    }
+
+    /* ------------------------- properties vs a bitmap ------------------------- */
+
+    const SIZE: usize = 64;
+
+    fn random_range(rng: &mut XorShift) -> Range<usize> {
+        let start = rng.below(SIZE);
+        start..(start + rng.below(12)).min(SIZE)
+    }
+
+    fn bitmap(set: &RangeSet<usize>) -> Vec<bool> {
+        let mut bits = vec![false; SIZE];
+        for range in set.iter() {
+            bits[range].fill(true);
+        }
+        bits
+    }
+
+    /// The start of the leftmost run of set bits that is at least `len` long.
+    fn first_fit(bits: &[bool], len: usize) -> Option<usize> {
+        (0..SIZE).find(|&i| {
+            (i == 0 || !bits[i - 1]) && i + len <= SIZE && bits[i..i + len].iter().all(|&b| b)
+        })
+    }
+
+    fn assert_normalized(set: &RangeSet<usize>) {
+        let ranges: Vec<_> = set.iter().collect();
+        assert_eq!(ranges.len(), set.len());
+        assert!(ranges.iter().all(|r| r.start < r.end));
+        assert!(ranges.windows(2).all(|w| w[0].end < w[1].start));
+    }
+
+    fn assert_arena(set: &RangeSet<usize>) {
+        assert_eq!(set.nodes.len() - set.free.len(), set.len());
+        assert_eq!(set.count(set.root), set.len());
+    }
+
+    #[test]
+    fn splits_like_bitmap() {
+        let mut rng = XorShift::new(0x8765_4321);
+
+        for _ in 0..200 {
+            let mut set: RangeSet<usize> = (0..20).map(|_| random_range(&mut rng)).collect();
+            let bits = bitmap(&set);
+
+            let at = rng.below(SIZE + 1);
+            let mut upper = set.split_off(at);
+            for half in [&set, &upper] {
+                assert_normalized(half);
+                assert_arena(half);
+            }
+
+            let mut lower_bits = bits.clone();
+            lower_bits[at..].fill(false);
+            let mut upper_bits = bits;
+            upper_bits[..at].fill(false);
+            assert_eq!(bitmap(&set), lower_bits);
+            assert_eq!(bitmap(&upper), upper_bits);
+
+            // the augmentation of both halves is intact.
+            for (half, bits) in [(&set, &lower_bits), (&upper, &upper_bits)] {
+                assert_eq!(half.coverage(), bits.iter().filter(|&&b| b).count());
+                for len in 1..=8 {
+                    assert_eq!(half.first_fit(len).map(|r| r.start), first_fit(bits, len));
+                }
+            }
+
+            // both halves keep working after the split.
+            let range = random_range(&mut rng);
+            upper.insert(range.clone());
+            upper_bits[range].fill(true);
+            assert_eq!(bitmap(&upper), upper_bits);
+            assert_arena(&upper);
+        }
+    }
+
+    #[test]
+    fn matches_bitmap() {
+        let mut rng = XorShift::new(0x1234_5678);
+
+        for _ in 0..200 {
+            let mut set = RangeSet::new();
+            let mut bits = vec![false; SIZE];
+
+            for _ in 0..30 {
+                let range = random_range(&mut rng);
+                if rng.below(3) == 0 {
+                    set.remove(range.clone());
+                    bits[range].fill(false);
+                } else {
+                    set.insert(range.clone());
+                    bits[range].fill(true);
+                }
+
+                assert_normalized(&set);
+                assert_eq!(bitmap(&set), bits);
+                assert_eq!(set.coverage(), bits.iter().filter(|&&b| b).count());
+
+                let query = random_range(&mut rng);
+                let covered = bits[query.clone()].iter().filter(|&&b| b).count();
+                assert_eq!(set.coverage_of(query), covered);
+
+                let x = rng.below(SIZE);
+                assert_eq!(set.contains(x), bits[x]);
+
+                let len = rng.below(8) + 1;
+                assert_eq!(set.first_fit(len).map(|r| r.start), first_fit(&bits, len));
+            }
+
+            let other: RangeSet<usize> = (0..5).map(|_| random_range(&mut rng)).collect();
+            let other_bits = bitmap(&other);
+
+            let both: Vec<bool> = (0..SIZE).map(|i| bits[i] && other_bits[i]).collect();
+            let intersection = set.intersection(&other);
+            assert_normalized(&intersection);
+            assert_eq!(bitmap(&intersection), both);
+
+            let either: Vec<bool> = (0..SIZE).map(|i| bits[i] || other_bits[i]).collect();
+            let union = set.union(&other);
+            assert_normalized(&union);
+            assert_eq!(bitmap(&union), either);
+
+            let at = rng.below(SIZE);
+            let upper = set.split_off(at);
+            assert_normalized(&set);
+            assert_normalized(&upper);
+            assert_eq!(bitmap(&set)[..at], bits[..at]);
+            assert!(bitmap(&set)[at..].iter().all(|&b| !b));
+            assert!(bitmap(&upper)[..at].iter().all(|&b| !b));
+            assert_eq!(bitmap(&upper)[at..], bits[at..]);
+        }
+    }
+}
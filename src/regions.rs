@@ -0,0 +1,268 @@
+//! Connected regions of a grid, e.g. the garden plots of the same plant.
+//!
+//! Cells belong to the same region if they are orthogonally adjacent and have the same value. [`label`] assigns a
+//! region to every cell and measures each region:
+//!
+//! ```ignore
+//! let price: usize = regions::label(&grid).iter().map(|r| r.area * r.perimeter).sum();
+//! ```
+use std::collections::VecDeque;
+
+use ndarray::Array2;
+
+use crate::disjoint_set::DisjointSet;
+use crate::geometry::{Direction, Vec2, NEIGHBORS_8};
+
+/// A connected region and its measurements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    /// Regions are labelled `0..n` in the order their first cell appears in the grid, row by row.
+    pub label: usize,
+    /// Number of cells.
+    pub area: usize,
+    /// Number of cell edges that border another region or the edge of the grid.
+    pub perimeter: usize,
+    /// Number of straight sides of the outline, including the outlines of holes. Equal to the number of corners.
+    pub sides: usize,
+    /// Top-left corner of the bounding box.
+    pub min: Vec2,
+    /// Bottom-right corner of the bounding box, inclusive.
+    pub max: Vec2,
+}
+
+/// The regions of a grid, along with the region of every cell.
+#[derive(Clone, Debug)]
+pub struct Regions {
+    width: usize,
+    height: usize,
+    labels: Vec<usize>,
+    regions: Vec<Region>,
+}
+
+/// Label the regions of cells with equal values.
+#[must_use]
+pub fn label<T: PartialEq>(grid: &Array2<T>) -> Regions {
+    label_by(grid.ncols(), grid.nrows(), |a, b| grid[a] == grid[b])
+}
+
+/// Label the regions of a `width` x `height` grid, where `same` tells whether two adjacent `(row, col)` cells belong
+/// to the same region.
+pub fn label_by(
+    width: usize,
+    height: usize,
+    same: impl Fn((usize, usize), (usize, usize)) -> bool,
+) -> Regions {
+    let mut cells = DisjointSet::from_grid_by(width, height, same);
+
+    // number the sets in order of appearance.
+    let mut compact = vec![usize::MAX; cells.len()];
+    let mut count = 0;
+    let labels: Vec<usize> = (0..cells.len())
+        .map(|i| {
+            let root = cells.find(i);
+            if compact[root] == usize::MAX {
+                compact[root] = count;
+                count += 1;
+            }
+            compact[root]
+        })
+        .collect();
+
+    let mut regions = Regions {
+        width,
+        height,
+        labels,
+        regions: vec![],
+    };
+    regions.measure(count);
+    regions
+}
+
+impl Regions {
+    fn measure(&mut self, count: usize) {
+        self.regions = (0..count)
+            .map(|label| Region {
+                label,
+                area: 0,
+                perimeter: 0,
+                sides: 0,
+                min: Vec2::new(isize::MAX, isize::MAX),
+                max: Vec2::new(isize::MIN, isize::MIN),
+            })
+            .collect();
+
+        for (i, &label) in self.labels.iter().enumerate() {
+            let pos = Vec2::from_index((i / self.width, i % self.width));
+            let same = |p: Vec2| self.label(p) == Some(label);
+
+            let mut perimeter = 0;
+            let mut corners = 0;
+            for dir in Direction::ALL {
+                let a = pos + dir.delta();
+                let b = pos + dir.turn_right().delta();
+
+                if !same(a) {
+                    perimeter += 1;
+                }
+
+                // an outside corner has neither neighbor in the region, an inside corner has both but not the diagonal.
+                if (!same(a) && !same(b)) || (same(a) && same(b) && !same(a + b - pos)) {
+                    corners += 1;
+                }
+            }
+
+            let region = &mut self.regions[label];
+            region.area += 1;
+            region.perimeter += perimeter;
+            region.sides += corners;
+            region.min = Vec2::new(region.min.x.min(pos.x), region.min.y.min(pos.y));
+            region.max = Vec2::new(region.max.x.max(pos.x), region.max.y.max(pos.y));
+        }
+    }
+
+    /// The label of the region that `pos` belongs to, `None` if it is off the grid.
+    #[must_use]
+    pub fn label(&self, pos: Vec2) -> Option<usize> {
+        pos.to_flat_index(self.width, self.height)
+            .map(|i| self.labels[i])
+    }
+
+    #[must_use]
+    pub fn get(&self, label: usize) -> Option<&Region> {
+        self.regions.get(label)
+    }
+
+    /// The region that `pos` belongs to.
+    #[must_use]
+    pub fn region_at(&self, pos: Vec2) -> Option<&Region> {
+        self.label(pos).map(|label| &self.regions[label])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// The cells of a region, row by row.
+    pub fn cells(&self, label: usize) -> impl Iterator<Item = Vec2> + '_ {
+        self.labels
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &l)| l == label)
+            .map(|(i, _)| Vec2::from_index((i / self.width, i % self.width)))
+    }
+
+    /// The number of holes in a region: groups of cells of other regions that it encloses completely.
+    /// Cells that only touch diagonally belong to the same hole. This scans the bounding box of the region.
+    #[must_use]
+    pub fn holes(&self, label: usize) -> usize {
+        let Some(region) = self.get(label) else {
+            return 0;
+        };
+
+        // the bounding box with a margin of one cell, so everything outside the region is connected around it.
+        let origin = region.min - Vec2::new(1, 1);
+        let size = region.max - region.min + Vec2::new(3, 3);
+        let (width, height) = (size.x.unsigned_abs(), size.y.unsigned_abs());
+
+        let mut seen: Vec<bool> = (0..width * height)
+            .map(|i| self.label(origin + Vec2::from_index((i / width, i % width))) == Some(label))
+            .collect();
+
+        let fill = |start: usize, seen: &mut Vec<bool>| {
+            let mut queue = VecDeque::from([start]);
+            seen[start] = true;
+            while let Some(i) = queue.pop_front() {
+                let pos = Vec2::from_index((i / width, i % width));
+                for next in NEIGHBORS_8.iter().map(|&d| pos + d) {
+                    if let Some(j) = next.to_flat_index(width, height) {
+                        if !seen[j] {
+                            seen[j] = true;
+                            queue.push_back(j);
+                        }
+                    }
+                }
+            }
+        };
+
+        fill(0, &mut seen);
+
+        let mut holes = 0;
+        for i in 0..seen.len() {
+            if !seen[i] {
+                fill(i, &mut seen);
+                holes += 1;
+            }
+        }
+        holes
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Array2<char> {
+        let rows: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+        Array2::from_shape_fn((rows.len(), rows[0].len()), |(r, c)| rows[r][c])
+    }
+
+    #[test]
+    fn labels_regions() {
+        let regions = label(&grid("AAAA\nBBCD\nBBCC\nEEEC"));
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions.label(Vec2::new(0, 0)), Some(0));
+        assert_eq!(
+            regions.label(Vec2::new(3, 3)),
+            regions.label(Vec2::new(2, 1))
+        );
+        assert_eq!(regions.label(Vec2::new(4, 0)), None);
+
+        let c = regions.region_at(Vec2::new(2, 1)).unwrap();
+        assert_eq!((c.area, c.perimeter, c.sides), (4, 10, 8));
+        assert_eq!((c.min, c.max), (Vec2::new(2, 1), Vec2::new(3, 3)));
+        assert_eq!(regions.cells(c.label).count(), 4);
+
+        let price: usize = regions.iter().map(|r| r.area * r.perimeter).sum();
+        assert_eq!(price, 140);
+        let price: usize = regions.iter().map(|r| r.area * r.sides).sum();
+        assert_eq!(price, 80);
+    }
+
+    #[test]
+    fn merges_labels_of_one_region() {
+        // the arms of the U are labelled separately until the bottom row joins them.
+        let regions = label(&grid("A.A\nA.A\nAAA"));
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions.get(0).unwrap().area, 7);
+        assert_eq!(regions.get(0).unwrap().sides, 8);
+        assert_eq!(regions.get(1).unwrap().label, 1);
+    }
+
+    #[test]
+    fn counts_holes() {
+        let regions = label(&grid("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO"));
+        let o = regions.get(0).unwrap();
+        assert_eq!((o.area, o.perimeter, o.sides), (21, 36, 20));
+        assert_eq!(regions.holes(0), 4);
+        assert_eq!(regions.holes(1), 0);
+
+        // diagonally adjacent cells form one hole.
+        let regions = label(&grid("AAAA\nABAA\nAABA\nAAAA"));
+        assert_eq!(regions.holes(0), 1);
+        // a region on the edge of the grid does not enclose what is outside the grid.
+        let regions = label(&grid("AAA\nA.A"));
+        assert_eq!(regions.holes(0), 0);
+    }
+}
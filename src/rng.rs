@@ -0,0 +1,51 @@
+//! A small, seeded pseudo-random number generator for values that only need to look random, e.g. treap priorities
+//! or the inputs of randomized tests.
+//!
+//! This is Marsaglia's xorshift64: fast and reproducible, but not suitable for anything that needs statistical
+//! quality or security.
+
+/// A xorshift64 generator. The same seed always gives the same sequence.
+#[derive(Clone, Debug)]
+pub struct XorShift(u64);
+
+impl XorShift {
+    /// A generator starting from `seed`. A seed of 0 would only ever give 0, so it is replaced by a fixed value.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self(if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        })
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A value in `0..n`, which must not be empty. Slightly biased towards small values unless `n` is a power of two.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_reproducible() {
+        let mut a = XorShift::new(42);
+        let mut b = XorShift::new(42);
+        assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+
+        let mut zero = XorShift::new(0);
+        assert_ne!(zero.next_u64(), 0);
+        assert!((0..100).all(|_| zero.below(7) < 7));
+    }
+}
@@ -0,0 +1,714 @@
+//! Graph searches over implicit graphs, e.g. the cells of a grid.
+//!
+//! A graph is anything that implements [`Neighbors`] (or [`WeightedNeighbors`] for searches with costs). Closures
+//! returning an iterator implement both, so a grid graph can be written without collecting the neighbors of
+//! every node into a `Vec`:
+//!
+//! ```ignore
+//! let graph = |p: &Vec2| p.neighbors_4().filter(|n| is_open(n));
+//! let path = search::bfs(start, graph, |p| *p == goal);
+//! ```
+//!
+//! Wrap a graph with [`observe`] to get a callback for every node that a search expands.
+use std::cell::RefCell;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A graph that lists the nodes adjacent to a node.
+pub trait Neighbors<N> {
+    fn neighbors(&self, node: &N) -> impl Iterator<Item = N>;
+}
+
+/// A graph that lists the nodes adjacent to a node, along with the cost of moving to them.
+pub trait WeightedNeighbors<N, C> {
+    fn edges(&self, node: &N) -> impl Iterator<Item = (N, C)>;
+}
+
+impl<N, F, I> Neighbors<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    fn neighbors(&self, node: &N) -> impl Iterator<Item = N> {
+        self(node).into_iter()
+    }
+}
+
+impl<N, C, F, I> WeightedNeighbors<N, C> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    fn edges(&self, node: &N) -> impl Iterator<Item = (N, C)> {
+        self(node).into_iter()
+    }
+}
+
+/// Costs of weighted searches. `Default` is the cost of not moving at all.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/* -------------------------------------------------------------------------- */
+
+/// A graph that calls an observer whenever a search expands one of its nodes.
+pub struct Observed<G, F> {
+    graph: G,
+    observer: RefCell<F>,
+}
+
+/// Call `observer` for every node that is expanded when searching `graph`, e.g. to count or draw visited nodes.
+pub fn observe<G, F>(graph: G, observer: F) -> Observed<G, F> {
+    Observed {
+        graph,
+        observer: RefCell::new(observer),
+    }
+}
+
+impl<N, G, F> Neighbors<N> for Observed<G, F>
+where
+    G: Neighbors<N>,
+    F: FnMut(&N),
+{
+    fn neighbors(&self, node: &N) -> impl Iterator<Item = N> {
+        (self.observer.borrow_mut())(node);
+        self.graph.neighbors(node)
+    }
+}
+
+impl<N, C, G, F> WeightedNeighbors<N, C> for Observed<G, F>
+where
+    G: WeightedNeighbors<N, C>,
+    F: FnMut(&N),
+{
+    fn edges(&self, node: &N) -> impl Iterator<Item = (N, C)> {
+        (self.observer.borrow_mut())(node);
+        self.graph.edges(node)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The nodes reached by a search, with the node each was reached from and the cost (or depth) to reach it.
+#[derive(Clone, Debug)]
+pub struct SearchTree<N, C> {
+    nodes: HashMap<N, (Option<N>, C)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> SearchTree<N, C> {
+    fn new(start: N, cost: C) -> Self {
+        Self {
+            nodes: HashMap::from([(start, (None, cost))]),
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    /// The cost to reach `node`, which is the number of steps for unweighted searches.
+    #[must_use]
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.nodes.get(node).map(|(_, cost)| *cost)
+    }
+
+    /// The node that `node` was reached from, `None` for the start and for nodes that were not reached.
+    #[must_use]
+    pub fn parent(&self, node: &N) -> Option<&N> {
+        self.nodes.get(node).and_then(|(parent, _)| parent.as_ref())
+    }
+
+    /// The path from the start to `node`, including both.
+    #[must_use]
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![];
+        let mut cur = Some(self.nodes.get_key_value(node)?.0);
+
+        while let Some(node) = cur {
+            path.push(node.clone());
+            cur = self.parent(node);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// All reached nodes with their costs, in no particular order.
+    pub fn nodes(&self) -> impl Iterator<Item = (&N, C)> {
+        self.nodes.iter().map(|(node, (_, cost))| (node, *cost))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Breadth-first search, stopping at the first node that satisfies `is_goal`.
+/// Returns the shortest path to it, including `start` and the goal.
+pub fn bfs<N, G>(start: N, graph: G, is_goal: impl FnMut(&N) -> bool) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    let (tree, goal) = bfs_until(start, &graph, is_goal);
+    tree.path_to(&goal?)
+}
+
+/// Breadth-first search of all nodes reachable from `start`. Costs are the number of steps from `start`.
+pub fn bfs_tree<N, G>(start: N, graph: G) -> SearchTree<N, usize>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    bfs_until(start, &graph, |_| false).0
+}
+
+fn bfs_until<N, G>(
+    start: N,
+    graph: &G,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (SearchTree<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    let mut tree = SearchTree::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return (tree, Some(node));
+        }
+
+        let depth = tree.cost(&node).unwrap_or_default() + 1;
+        for next in graph.neighbors(&node) {
+            if !tree.contains(&next) {
+                tree.nodes.insert(next.clone(), (Some(node.clone()), depth));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    (tree, None)
+}
+
+/// Depth-first search, stopping at the first node that satisfies `is_goal`.
+/// Returns the path to it, which is not necessarily the shortest.
+pub fn dfs<N, G>(start: N, graph: G, is_goal: impl FnMut(&N) -> bool) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    let (tree, goal) = dfs_until(start, &graph, is_goal);
+    tree.path_to(&goal?)
+}
+
+/// Depth-first search of all nodes reachable from `start`. Costs are the depth in the search tree.
+pub fn dfs_tree<N, G>(start: N, graph: G) -> SearchTree<N, usize>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    dfs_until(start, &graph, |_| false).0
+}
+
+fn dfs_until<N, G>(
+    start: N,
+    graph: &G,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (SearchTree<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    let mut tree = SearchTree {
+        nodes: HashMap::new(),
+    };
+    let mut stack = vec![(start, None, 0)];
+
+    while let Some((node, parent, depth)) = stack.pop() {
+        if tree.contains(&node) {
+            continue;
+        }
+        tree.nodes.insert(node.clone(), (parent, depth));
+
+        if is_goal(&node) {
+            return (tree, Some(node));
+        }
+
+        for next in graph.neighbors(&node) {
+            if !tree.contains(&next) {
+                stack.push((next, Some(node.clone()), depth + 1));
+            }
+        }
+    }
+
+    (tree, None)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An entry of the priority queue, ordered by its priority only.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+/// A* search, expanding nodes in order of cost plus `heuristic`. With a zero heuristic this is Dijkstra's algorithm.
+/// The heuristic must not overestimate the remaining cost, otherwise the returned path may not be the cheapest.
+fn best_first<N, C, G>(
+    start: N,
+    graph: &G,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (SearchTree<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+{
+    let mut tree = SearchTree::new(start.clone(), C::default());
+    let mut queue = BinaryHeap::from([Reverse(Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    })]);
+
+    while let Some(Reverse(Queued { cost, node, .. })) = queue.pop() {
+        // skip stale entries, the node was reached more cheaply since.
+        if tree.cost(&node).is_some_and(|best| best < cost) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return (tree, Some(node));
+        }
+
+        for (next, step) in graph.edges(&node) {
+            let next_cost = cost + step;
+            if tree.cost(&next).is_none_or(|best| next_cost < best) {
+                tree.nodes
+                    .insert(next.clone(), (Some(node.clone()), next_cost));
+                queue.push(Reverse(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                }));
+            }
+        }
+    }
+
+    (tree, None)
+}
+
+/// Dijkstra's algorithm, stopping at the first node that satisfies `is_goal`.
+/// Returns the cheapest path to it along with its cost.
+pub fn dijkstra<N, C, G>(start: N, graph: G, is_goal: impl FnMut(&N) -> bool) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+{
+    let (tree, goal) = best_first(start, &graph, |_| C::default(), is_goal);
+    let goal = goal?;
+    Some((tree.path_to(&goal)?, tree.cost(&goal)?))
+}
+
+/// Dijkstra's algorithm over all nodes reachable from `start`. Costs are the cheapest cost to reach each node.
+pub fn dijkstra_tree<N, C, G>(start: N, graph: G) -> SearchTree<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+{
+    best_first(start, &graph, |_| C::default(), |_| false).0
+}
+
+/// A* search, stopping at the first node that satisfies `is_goal`. Returns the cheapest path to it along with its
+/// cost, as long as `heuristic` never overestimates the cost that remains to reach a goal.
+pub fn astar<N, C, G>(
+    start: N,
+    graph: G,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+{
+    let (tree, goal) = best_first(start, &graph, heuristic, is_goal);
+    let goal = goal?;
+    Some((tree.path_to(&goal)?, tree.cost(&goal)?))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// All cheapest paths from `start` to the goals that can be reached most cheaply, along with their cost.
+/// The number of paths can grow exponentially, use [`count_shortest_paths`] if only their number is needed.
+pub fn all_shortest_paths<N, C, G>(
+    start: N,
+    graph: G,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<Vec<N>>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+{
+    let (parents, goals, cost) = shortest_path_dag(start, &graph, is_goal)?;
+
+    let mut paths = vec![];
+    let mut stack: Vec<Vec<N>> = goals.into_iter().map(|goal| vec![goal]).collect();
+
+    while let Some(path) = stack.pop() {
+        let last = path.last().expect("paths are never empty");
+        match parents.get(last) {
+            Some((prev, _)) if !prev.is_empty() => {
+                for prev in prev {
+                    let mut path = path.clone();
+                    path.push(prev.clone());
+                    stack.push(path);
+                }
+            }
+            _ => {
+                let mut path = path;
+                path.reverse();
+                paths.push(path);
+            }
+        }
+    }
+
+    Some((paths, cost))
+}
+
+/// The number of cheapest paths from `start` to the goals that can be reached most cheaply, along with their cost.
+/// Edges may cost nothing, but the graph must not have cycles of zero cost, which would allow infinitely many paths.
+pub fn count_shortest_paths<N, C, G>(
+    start: N,
+    graph: G,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(usize, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+{
+    let (parents, goals, cost) = shortest_path_dag(start.clone(), &graph, is_goal)?;
+
+    // count paths backwards from each goal. a node can gain predecessors after it was expanded when edges cost
+    // nothing, so neither the cost nor the expansion order are safe to count in.
+    let predecessors = |node: &N| parents.get(node).map_or(vec![], |(prev, _)| prev.clone());
+    let mut counts = HashMap::new();
+    let paths = goals
+        .into_iter()
+        .map(|goal| count_paths_from(goal, &predecessors, &mut |n| *n == start, &mut counts))
+        .sum();
+
+    Some((paths, cost))
+}
+
+type Predecessors<N, C> = HashMap<N, (Vec<N>, C)>;
+
+/// Dijkstra's algorithm that remembers every predecessor on a cheapest path, not just the first one.
+/// Explores until all goals at the cheapest goal cost are found.
+fn shortest_path_dag<N, C, G>(
+    start: N,
+    graph: &G,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Predecessors<N, C>, Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+{
+    let mut parents: Predecessors<N, C> = HashMap::from([(start.clone(), (vec![], C::default()))]);
+    let mut queue = BinaryHeap::from([Reverse(Queued {
+        priority: C::default(),
+        cost: C::default(),
+        node: start,
+    })]);
+    let mut goals = vec![];
+    let mut goal_cost = None;
+
+    while let Some(Reverse(Queued { cost, node, .. })) = queue.pop() {
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        if parents[&node].1 < cost {
+            continue;
+        }
+
+        if is_goal(&node) {
+            goal_cost = Some(cost);
+            goals.push(node);
+            continue;
+        }
+
+        for (next, step) in graph.edges(&node) {
+            let next_cost = cost + step;
+            match parents.get_mut(&next) {
+                Some((prev, best)) if next_cost == *best => prev.push(node.clone()),
+                Some((_, best)) if next_cost > *best => {}
+                _ => {
+                    parents.insert(next.clone(), (vec![node.clone()], next_cost));
+                    queue.push(Reverse(Queued {
+                        priority: next_cost,
+                        cost: next_cost,
+                        node: next,
+                    }));
+                }
+            }
+        }
+    }
+
+    // nodes that are only reachable at a higher cost than the goals are not part of any cheapest path.
+    let goal_cost = goal_cost?;
+    parents.retain(|_, (_, cost)| *cost <= goal_cost);
+    Some((parents, goals, goal_cost))
+}
+
+/// The number of distinct paths from `start` to nodes that satisfy `is_goal`. Paths may continue past a goal to
+/// reach further goals. The graph must not have cycles reachable from `start`.
+pub fn count_paths<N, G>(start: N, graph: G, mut is_goal: impl FnMut(&N) -> bool) -> usize
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    count_paths_from(start, &graph, &mut is_goal, &mut HashMap::new())
+}
+
+/// Depth-first with an explicit stack, so long chains of nodes do not overflow the call stack. `counts` holds the
+/// number of paths from every finished node and can be shared between calls on the same graph.
+fn count_paths_from<N, G>(
+    start: N,
+    graph: &G,
+    is_goal: &mut impl FnMut(&N) -> bool,
+    counts: &mut HashMap<N, usize>,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    // a node is entered without its successors, and left once all of them are counted.
+    let mut stack: Vec<(N, Option<Vec<N>>)> = vec![(start.clone(), None)];
+
+    while let Some((node, next)) = stack.pop() {
+        match next {
+            None => {
+                if counts.contains_key(&node) {
+                    continue;
+                }
+                // a placeholder until the node is left, so a cycle ends here instead of looping forever.
+                counts.insert(node.clone(), 0);
+
+                let next: Vec<N> = graph.neighbors(&node).collect();
+                let enter: Vec<(N, Option<Vec<N>>)> = next
+                    .iter()
+                    .filter(|n| !counts.contains_key(n))
+                    .map(|n| (n.clone(), None))
+                    .collect();
+                stack.push((node, Some(next)));
+                stack.extend(enter);
+            }
+            Some(next) => {
+                let paths =
+                    usize::from(is_goal(&node)) + next.iter().map(|n| counts[n]).sum::<usize>();
+                counts.insert(node, paths);
+            }
+        }
+    }
+
+    counts[&start]
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::geometry::Vec2;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.##...#E";
+
+    fn parse_maze() -> (Vec<Vec<char>>, Vec2, Vec2) {
+        let grid: Vec<Vec<char>> = MAZE.lines().map(|l| l.chars().collect()).collect();
+        let find = |c| {
+            grid.iter()
+                .enumerate()
+                .find_map(|(y, row)| row.iter().position(|&v| v == c).map(|x| (y, x)))
+                .map(Vec2::from_index)
+                .unwrap()
+        };
+        let (start, end) = (find('S'), find('E'));
+        (grid, start, end)
+    }
+
+    fn open(grid: &[Vec<char>], p: Vec2) -> bool {
+        p.to_index(grid[0].len(), grid.len())
+            .is_some_and(|(row, col)| grid[row][col] != '#')
+    }
+
+    #[test]
+    fn finds_shortest_paths() {
+        let (grid, start, end) = parse_maze();
+        let graph = |p: &Vec2| p.neighbors_4().filter(|&n| open(&grid, n));
+
+        let path = bfs(start, graph, |p| *p == end).unwrap();
+        assert_eq!(path.len() - 1, 12);
+        assert_eq!((path[0], path[path.len() - 1]), (start, end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        let tree = bfs_tree(start, graph);
+        assert_eq!(tree.cost(&end), Some(12));
+        assert_eq!(tree.path_to(&end).unwrap().len(), path.len());
+        assert_eq!(tree.len(), 22);
+
+        let path = dfs(start, graph, |p| *p == end).unwrap();
+        assert!(path.len() > 12);
+        assert_eq!(dfs_tree(start, graph).len(), 22);
+        assert_eq!(bfs(start, graph, |p| p.x < 0), None);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let (grid, start, end) = parse_maze();
+        // moving down is expensive.
+        let graph = |p: &Vec2| {
+            let p = *p;
+            p.neighbors_4()
+                .filter(|&n| open(&grid, n))
+                .map(move |n| (n, if n.y > p.y { 5 } else { 1 }))
+        };
+
+        let (path, cost) = dijkstra(start, graph, |p| *p == end).unwrap();
+        assert_eq!(cost, 28);
+        assert_eq!(path.last(), Some(&end));
+
+        let (path, cost) = astar(start, graph, |p| p.manhattan(end), |p| *p == end).unwrap();
+        assert_eq!(cost, 28);
+        assert_eq!(path.last(), Some(&end));
+
+        assert_eq!(dijkstra_tree(start, graph).cost(&end), Some(28));
+    }
+
+    #[test]
+    fn finds_all_shortest_paths() {
+        // a 3x3 grid has 6 shortest paths between opposite corners.
+        let graph = |p: &Vec2| {
+            p.neighbors_4()
+                .filter(|n| n.to_index(3, 3).is_some())
+                .map(|n| (n, 1))
+        };
+        let end = Vec2::new(2, 2);
+
+        let (paths, cost) = all_shortest_paths(Vec2::new(0, 0), graph, |p| *p == end).unwrap();
+        assert_eq!((paths.len(), cost), (6, 4));
+        assert!(paths.iter().all(|path| path.len() == 5));
+        assert_eq!(
+            count_shortest_paths(Vec2::new(0, 0), graph, |p| *p == end),
+            Some((6, 4))
+        );
+    }
+
+    #[test]
+    fn counts_paths() {
+        // only move right or down: C(4, 2) paths through a 3x3 grid.
+        let graph = |p: &Vec2| {
+            [*p + Vec2::new(1, 0), *p + Vec2::new(0, 1)]
+                .into_iter()
+                .filter(|n| n.to_index(3, 3).is_some())
+        };
+        assert_eq!(
+            count_paths(Vec2::new(0, 0), graph, |p| p.x == 2 && p.y == 2),
+            6
+        );
+        assert_eq!(count_paths(Vec2::new(0, 0), graph, |p| p.x == 2), 10);
+    }
+
+    #[test]
+    fn counts_shortest_paths_over_free_edges() {
+        // 0 -> 2 and 0 -> 1 -> 2 cost the same, 1 is settled after 2 would be by cost alone.
+        let edges = [
+            (0, 1, 1),
+            (0, 2, 1),
+            (1, 2, 0),
+            (2, 3, 1),
+            (0, 4, 2),
+            (4, 3, 0),
+        ];
+        let graph = |n: &u32| {
+            let n = *n;
+            edges.iter().filter(move |e| e.0 == n).map(|e| (e.1, e.2))
+        };
+        assert_eq!(count_shortest_paths(0, graph, |n| *n == 3), Some((3, 2)));
+        assert_eq!(
+            all_shortest_paths(0, graph, |n| *n == 3).map(|(paths, _)| paths.len()),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn counts_paths_along_long_chains() {
+        let chain = |n: &u32| (*n < 100_000).then_some(n + 1);
+        assert_eq!(count_paths(0, chain, |n| *n == 100_000), 1);
+        assert_eq!(count_paths(0, chain, |n| n % 1000 == 0), 101);
+
+        let graph = |n: &u32| (*n < 100_000).then_some((n + 1, 0));
+        assert_eq!(
+            count_shortest_paths(0, graph, |n| *n == 100_000),
+            Some((1, 0))
+        );
+    }
+
+    #[test]
+    fn observes_expanded_nodes() {
+        let (grid, start, end) = parse_maze();
+        let mut expanded = vec![];
+        let graph = observe(
+            |p: &Vec2| p.neighbors_4().filter(|&n| open(&grid, n)),
+            |p: &Vec2| expanded.push(*p),
+        );
+
+        bfs(start, graph, |p| *p == end);
+        assert_eq!(expanded[0], start);
+        assert!(!expanded.contains(&end));
+        assert!(expanded.len() < 22);
+    }
+}
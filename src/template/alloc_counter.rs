@@ -0,0 +1,86 @@
+/// A global allocator that counts allocations, as a lightweight alternative to dhat.
+/// Installed by the `solution!` macro when the `count-allocs` feature is enabled.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ops::Sub;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator and counts every allocation and the bytes requested by it.
+/// Reallocations count as an allocation of the additional bytes.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size.saturating_sub(layout.size()));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+fn record(bytes: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+/// Allocation counts at a point in time, or the difference between two points in time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+impl Sub for AllocStats {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        AllocStats {
+            allocations: self.allocations.saturating_sub(rhs.allocations),
+            bytes: self.bytes.saturating_sub(rhs.bytes),
+        }
+    }
+}
+
+/// Returns the allocations counted so far. Always zero if [`CountingAlloc`] is not installed.
+pub fn snapshot() -> AllocStats {
+    AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{snapshot, CountingAlloc};
+    use std::alloc::{GlobalAlloc, Layout};
+
+    #[test]
+    fn counts_allocations() {
+        let layout = Layout::from_size_align(1024, 8).unwrap();
+        let before = snapshot();
+
+        unsafe {
+            let ptr = CountingAlloc.alloc(layout);
+            let ptr = CountingAlloc.realloc(ptr, layout, 4096);
+            CountingAlloc.dealloc(ptr, Layout::from_size_align(4096, 8).unwrap());
+        }
+
+        let stats = snapshot() - before;
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.bytes, 4096);
+    }
+}
@@ -0,0 +1,331 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{profile, Day};
+
+/// The response of the advent of code website to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    /// The part was solved before. This says nothing about the submitted answer, which stays unconfirmed.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Parses the verdict from the output of `aoc submit`.
+    pub fn from_output(output: &str) -> Option<Self> {
+        if output.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if output.contains("That's not the right answer") {
+            Some(Verdict::Incorrect)
+        } else if output.contains("already complete it") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::AlreadySolved => "already solved",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "already solved" => Ok(Verdict::AlreadySolved),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// Represents the last answer computed for a single part of a day.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Option<Verdict>,
+}
+
+/// Represents stored answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers of the active profile to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let path = profile::get_answers_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers of the active profile from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(profile::get_answers_path())
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&Answer> {
+        self.data.iter().find(|a| a.day == day && a.part == part)
+    }
+
+    /// Record an answer. A verdict is kept only if the answer did not change.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str) {
+        match self
+            .data
+            .iter_mut()
+            .find(|a| a.day == day && a.part == part)
+        {
+            Some(existing) if existing.answer == answer => {}
+            Some(existing) => {
+                existing.answer = answer.into();
+                existing.verdict = None;
+            }
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part,
+                    answer: answer.into(),
+                    verdict: None,
+                });
+                self.data.sort_unstable_by_key(|a| (a.day, a.part));
+            }
+        }
+    }
+
+    /// Record an answer along with the verdict it received on submission.
+    /// [`Verdict::AlreadySolved`] does not replace an earlier verdict for the same answer.
+    pub fn record_verdict(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        self.record(day, part, answer);
+        if let Some(existing) = self
+            .data
+            .iter_mut()
+            .find(|a| a.day == day && a.part == part)
+        {
+            if verdict != Verdict::AlreadySolved || existing.verdict.is_none() {
+                existing.verdict = Some(verdict);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            match value.verdict {
+                Some(verdict) => JsonValue::String(verdict.as_str().into()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .filter(|part| day.has_part(*part))
+            .ok_or("Expected answer.part to be a part of the day.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.answer to be a string.")?;
+
+        let verdict = match json.get("verdict") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected answer.verdict to be null or string.")?
+                    .parse()?,
+            ),
+        };
+
+        Ok(Answer {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Answers, Verdict};
+
+    #[test]
+    fn handles_json_answers() {
+        let json =
+            r#"{ "data": [{ "day": "01", "part": 2, "answer": "42", "verdict": "correct" }] }"#
+                .to_string();
+        let answers = Answers::try_from(json).unwrap();
+        let answer = answers.get(day!(1), 2).unwrap();
+        assert_eq!(answer.answer, "42");
+        assert_eq!(answer.verdict, Some(Verdict::Correct));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_nonexistent_parts() {
+        let json = r#"{ "data": [{ "day": "25", "part": 2, "answer": "42", "verdict": null }] }"#
+            .to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn keeps_verdict_for_unchanged_answers() {
+        let mut answers = Answers::default();
+        answers.record_verdict(day!(3), 1, "10", Verdict::Correct);
+        answers.record(day!(3), 1, "10");
+        assert_eq!(
+            answers.get(day!(3), 1).unwrap().verdict,
+            Some(Verdict::Correct)
+        );
+        answers.record(day!(3), 1, "11");
+        assert_eq!(answers.get(day!(3), 1).unwrap().verdict, None);
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(2), 1, "1");
+        answers.record_verdict(day!(1), 2, "2", Verdict::Incorrect);
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.data[0].verdict, Some(Verdict::Incorrect));
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_output("That's the right answer! You are one gold star closer."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::from_output("That's not the right answer; your answer is too high."),
+            Some(Verdict::Incorrect)
+        );
+        assert_eq!(
+            Verdict::from_output(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Some(Verdict::AlreadySolved)
+        );
+        assert_eq!(
+            Verdict::from_output("You gave an answer too recently"),
+            None
+        );
+    }
+
+    #[test]
+    fn does_not_confirm_answers_of_solved_parts() {
+        let mut answers = Answers::default();
+        answers.record_verdict(day!(4), 1, "7", Verdict::AlreadySolved);
+        assert_eq!(
+            answers.get(day!(4), 1).unwrap().verdict,
+            Some(Verdict::AlreadySolved)
+        );
+
+        // an earlier verdict for the same answer is kept.
+        answers.record_verdict(day!(4), 2, "8", Verdict::Incorrect);
+        answers.record_verdict(day!(4), 2, "8", Verdict::AlreadySolved);
+        assert_eq!(
+            answers.get(day!(4), 2).unwrap().verdict,
+            Some(Verdict::Incorrect)
+        );
+
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.data[0].verdict, Some(Verdict::AlreadySolved));
+    }
+}
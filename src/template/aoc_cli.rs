@@ -4,12 +4,14 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{profile, request_log, Day};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
+    SessionNotFound(String),
+    SubmitCooldown(u64),
     BadExitStatus(Output),
 }
 
@@ -18,6 +20,14 @@ impl Display for AocCommandError {
         match self {
             AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
+            AocCommandError::SessionNotFound(profile) => {
+                write!(f, "no session cookie found for profile `{profile}`.")
+            }
+            AocCommandError::SubmitCooldown(secs) => write!(
+                f,
+                "submitting is allowed again in {}.",
+                request_log::format_wait(*secs)
+            ),
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
@@ -33,62 +43,60 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
+/// Download the puzzle description, and the input to `input_path` unless it is `None`.
+pub fn download(day: Day, input_path: Option<&str>) -> Result<Output, AocCommandError> {
     let puzzle_path = get_puzzle_path(day);
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
-
-    call_aoc_cli(&args)
-}
+    let mut args = vec!["--overwrite".into(), "--puzzle-file".into(), puzzle_path];
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+    match input_path {
+        Some(input_path) => args.extend(["--input-file".into(), input_path.into()]),
+        None => args.push("--puzzle-only".into()),
+    }
 
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
-
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    request_log::throttle("download");
+    call_aoc_cli(&build_args("download", &args, day))
 }
 
+/// Submit an answer. Fails without contacting the website if it asked to wait before submitting again.
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+    if let Some(secs) = request_log::check_submit_cooldown() {
+        let e = AocCommandError::SubmitCooldown(secs);
+        eprintln!("You gave an answer too recently, {e}");
+        return Err(e);
+    }
+
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // the output is captured so callers can inspect the verdict, echo it for the user.
+    request_log::throttle("submit");
+    let output = call_aoc_cli_captured(&args)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    print!("{stdout}");
+
+    if let Some(wait) = request_log::parse_wait(&stdout) {
+        request_log::record_submit_cooldown(wait);
+        println!(
+            "Submitting is allowed again in {}.",
+            request_log::format_wait(wait.as_secs())
+        );
+    }
+
+    Ok(output)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+pub fn get_input_path(day: Day) -> String {
+    profile::get_input_path(day).to_string_lossy().into_owned()
 }
 
-fn get_puzzle_path(day: Day) -> String {
+pub fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -108,9 +116,22 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
+/// Build an aoc-cli command that uses the session of the active profile.
+/// The default profile leaves the session lookup to aoc-cli.
+fn aoc_command() -> Result<Command, AocCommandError> {
+    let mut cmd = Command::new("aoc");
+
+    if let Some(profile) = profile::get_profile() {
+        let session = profile::get_session().ok_or(AocCommandError::SessionNotFound(profile))?;
+        cmd.env("ADVENT_OF_CODE_SESSION", session);
+    }
+
+    Ok(cmd)
+}
+
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let output = aoc_command()?
         .args(args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -123,3 +144,18 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    let output = aoc_command()?
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
@@ -0,0 +1,320 @@
+/// Settings that control how solutions are benched by `cargo time`.
+/// Settings are resolved from defaults, then environment variables (e.g. set in `.cargo/config.toml`), then command-line flags.
+use std::{env, str::FromStr, time::Duration};
+
+/// z-score of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchConfig {
+    /// Runs before measuring, to warm up caches and branch predictors.
+    pub warmup: u32,
+    /// Time budget for benching, including the unmeasured setup of each run. Benching stops once it is used up and
+    /// `min_samples` were taken.
+    pub target_time: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Stop early once the 95% confidence interval of the mean is within this fraction of the mean, e.g. `0.01` for ±1%.
+    pub confidence: Option<f64>,
+    /// Clear registered global caches before every sample, so each sample runs on cold caches.
+    pub reset_caches: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 0,
+            target_time: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            confidence: None,
+            reset_caches: false,
+        }
+    }
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for {name}."))
+}
+
+impl BenchConfig {
+    /// Resolve settings from environment variables, falling back to defaults.
+    pub fn from_env() -> Result<Self, String> {
+        let mut config = Self::default();
+
+        let var = |name: &str| env::var(name).ok();
+
+        if let Some(v) = var("AOC_BENCH_WARMUP") {
+            config.warmup = parse_value("AOC_BENCH_WARMUP", &v)?;
+        }
+        if let Some(v) = var("AOC_BENCH_TIME_MS") {
+            config.target_time = Duration::from_millis(parse_value("AOC_BENCH_TIME_MS", &v)?);
+        }
+        if let Some(v) = var("AOC_BENCH_MIN_SAMPLES") {
+            config.min_samples = parse_value("AOC_BENCH_MIN_SAMPLES", &v)?;
+        }
+        if let Some(v) = var("AOC_BENCH_MAX_SAMPLES") {
+            config.max_samples = parse_value("AOC_BENCH_MAX_SAMPLES", &v)?;
+        }
+        if let Some(v) = var("AOC_BENCH_CONFIDENCE") {
+            config.confidence = Some(parse_value::<f64>("AOC_BENCH_CONFIDENCE", &v)? / 100.0);
+        }
+        if let Some(v) = var("AOC_BENCH_RESET_CACHES") {
+            config.reset_caches = parse_value("AOC_BENCH_RESET_CACHES", &v)?;
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Override settings with command-line flags, removing them from `args`.
+    pub fn apply_args(&mut self, args: &mut pico_args::Arguments) -> Result<(), String> {
+        let mut opt = |name: &'static str| -> Result<Option<String>, String> {
+            args.opt_value_from_str::<_, String>(name)
+                .map_err(|e| e.to_string())
+        };
+
+        if let Some(v) = opt("--warmup")? {
+            self.warmup = parse_value("--warmup", &v)?;
+        }
+        if let Some(v) = opt("--bench-time")? {
+            self.target_time = Duration::from_millis(parse_value("--bench-time", &v)?);
+        }
+        if let Some(v) = opt("--min-samples")? {
+            self.min_samples = parse_value("--min-samples", &v)?;
+        }
+        if let Some(v) = opt("--max-samples")? {
+            self.max_samples = parse_value("--max-samples", &v)?;
+        }
+        if let Some(v) = opt("--confidence")? {
+            self.confidence = Some(parse_value::<f64>("--confidence", &v)? / 100.0);
+        }
+        if args.contains("--reset-caches") {
+            self.reset_caches = true;
+        }
+
+        self.validate()
+    }
+
+    /// Resolve settings for a solution binary from its environment and arguments.
+    pub fn resolve() -> Result<Self, String> {
+        let mut config = Self::from_env()?;
+        config.apply_args(&mut pico_args::Arguments::from_env())?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.min_samples == 0 || self.min_samples > self.max_samples {
+            return Err(format!(
+                "expected 0 < min samples ({}) <= max samples ({}).",
+                self.min_samples, self.max_samples
+            ));
+        }
+
+        if let Some(confidence) = self.confidence {
+            if !(confidence > 0.0 && confidence < 1.0) {
+                return Err("expected confidence to be a percentage between 0 and 100.".into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Command-line flags that reproduce this configuration in a child process.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--warmup".into(),
+            self.warmup.to_string(),
+            "--bench-time".into(),
+            self.target_time.as_millis().to_string(),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ];
+
+        if let Some(confidence) = self.confidence {
+            args.push("--confidence".into());
+            args.push((confidence * 100.0).to_string());
+        }
+
+        if self.reset_caches {
+            args.push("--reset-caches".into());
+        }
+
+        args
+    }
+
+    /// Decide if benching can stop, given the samples taken so far and the time spent benching.
+    pub fn is_done(&self, stats: &SampleStats, elapsed: Duration) -> bool {
+        if stats.count >= self.max_samples {
+            return true;
+        }
+
+        if stats.count < self.min_samples {
+            return false;
+        }
+
+        let is_confident = self
+            .confidence
+            .is_some_and(|confidence| stats.relative_ci() <= confidence);
+
+        is_confident || elapsed >= self.target_time
+    }
+}
+
+/// Statistics of measured runs. Runs are measured in batches, the confidence interval is derived from batch means.
+/// Uses a running mean and variance, see: https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm
+#[derive(Clone, Debug, Default)]
+pub struct SampleStats {
+    /// Number of measured runs over all batches.
+    pub count: u128,
+    total_nanos: f64,
+    batches: u128,
+    batch_mean: f64,
+    m2: f64,
+}
+
+impl SampleStats {
+    pub fn push(&mut self, sample: Duration) {
+        self.push_batch(sample, 1);
+    }
+
+    /// Record a batch of `runs` that took `elapsed` in total.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn push_batch(&mut self, elapsed: Duration, runs: u128) {
+        if runs == 0 {
+            return;
+        }
+
+        let total = elapsed.as_nanos() as f64;
+        self.count += runs;
+        self.total_nanos += total;
+
+        let x = total / runs as f64;
+        self.batches += 1;
+        let delta = x - self.batch_mean;
+        self.batch_mean += delta / self.batches as f64;
+        self.m2 += delta * (x - self.batch_mean);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_nanos(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.total_nanos / self.count as f64
+        }
+    }
+
+    /// Half-width of the 95% confidence interval of the mean, relative to the mean.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn relative_ci(&self) -> f64 {
+        if self.batches < 2 || self.batch_mean <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        let n = self.batches as f64;
+        let std_dev = (self.m2 / (n - 1.0)).sqrt();
+        Z_95 * std_dev / n.sqrt() / self.batch_mean
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{ffi::OsString, time::Duration};
+
+    use super::{BenchConfig, SampleStats};
+
+    fn parse(args: &[&str]) -> Result<BenchConfig, String> {
+        let mut args = pico_args::Arguments::from_vec(args.iter().map(OsString::from).collect());
+        let mut config = BenchConfig::default();
+        config.apply_args(&mut args)?;
+        Ok(config)
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        let config = parse(&[
+            "--warmup",
+            "3",
+            "--bench-time",
+            "250",
+            "--min-samples",
+            "5",
+            "--max-samples",
+            "50",
+            "--confidence",
+            "2",
+            "--reset-caches",
+        ])
+        .unwrap();
+
+        assert_eq!(config.warmup, 3);
+        assert_eq!(config.target_time, Duration::from_millis(250));
+        assert_eq!(config.confidence, Some(0.02));
+        assert!(config.reset_caches);
+
+        let args = config.to_args();
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        assert_eq!(parse(&args).unwrap(), config);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_sample_bounds() {
+        parse(&["--min-samples", "100", "--max-samples", "10"]).unwrap();
+    }
+
+    #[test]
+    fn stops_within_sample_bounds() {
+        let config = BenchConfig::default();
+        let mut stats = SampleStats::default();
+
+        for _ in 0..9 {
+            stats.push(Duration::from_secs(1));
+        }
+        assert!(!config.is_done(&stats, Duration::from_secs(9)));
+
+        stats.push(Duration::from_secs(1));
+        assert!(config.is_done(&stats, Duration::from_secs(10)));
+
+        let mut stats = SampleStats::default();
+        for _ in 0..10000 {
+            stats.push(Duration::from_nanos(1));
+        }
+        assert!(config.is_done(&stats, Duration::from_nanos(10000)));
+    }
+
+    #[test]
+    fn stops_once_confident() {
+        let config = BenchConfig {
+            confidence: Some(0.01),
+            ..BenchConfig::default()
+        };
+
+        let mut stats = SampleStats::default();
+        for i in 0..20 {
+            stats.push(Duration::from_nanos(1000 + i % 2));
+        }
+
+        assert!(stats.relative_ci() < 0.01);
+        assert!(config.is_done(&stats, Duration::from_micros(20)));
+        assert!(!BenchConfig::default().is_done(&stats, Duration::from_micros(20)));
+    }
+
+    #[test]
+    fn counts_runs_of_batches() {
+        let mut stats = SampleStats::default();
+        stats.push_batch(Duration::from_nanos(1000), 100);
+        stats.push_batch(Duration::from_nanos(300), 10);
+
+        assert_eq!(stats.count, 110);
+        assert_eq!(stats.mean_nanos(), 1300.0 / 110.0);
+        // the confidence interval is based on two batch means (10ns and 30ns).
+        assert!((stats.relative_ci() - 0.98).abs() < 0.01);
+    }
+}
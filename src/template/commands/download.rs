@@ -1,14 +1,94 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::download_meta::{DownloadMeta, FileMeta};
+use crate::template::validate::check_placeholder;
+use crate::template::{aoc_cli, profile, Day};
+use std::{fs, path::Path, process};
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, overwrite: bool) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    let input_path = aoc_cli::get_input_path(day);
+    let puzzle_path = aoc_cli::get_puzzle_path(day);
+    let existing = fs::read(&input_path).ok();
+
+    // inputs do not change, only the puzzle description has to be fetched again once part one is solved.
+    if let (Some(existing), Some(mut meta)) = (&existing, DownloadMeta::read_from_file(day)) {
+        if !overwrite && meta.input.matches(existing) {
+            if let Err(e) = aoc_cli::download(day, None) {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+
+            meta.puzzle = fs::read(&puzzle_path).ok().map(|p| FileMeta::new(&p));
+            store_meta(&meta);
+
+            println!("---");
+            println!("🎄 Input \"{input_path}\" is unchanged, skipped downloading it.");
+            println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+            return;
+        }
+    }
+
+    // download to a separate file, so an existing input is only replaced when intended.
+    let download_path = format!("{input_path}.download");
+    let _ = fs::remove_file(&download_path);
+
+    if let Some(parent) = Path::new(&input_path).parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("failed to create input directory: {e}");
+            process::exit(1);
+        }
+    }
+
+    if let Err(e) = aoc_cli::download(day, Some(&download_path)) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    let downloaded = fs::read(&download_path).unwrap_or_else(|e| {
+        eprintln!("failed to read downloaded input: {e}");
+        process::exit(1);
+    });
+
+    let session = profile::get_session();
+    let mut meta = DownloadMeta::new(
+        day,
+        &profile::get_profile_name(),
+        session.as_deref(),
+        &downloaded,
+    );
+    meta.puzzle = fs::read(&puzzle_path).ok().map(|p| FileMeta::new(&p));
+    store_meta(&meta);
+
+    println!("---");
+
+    match existing {
+        Some(existing) if existing == downloaded => {
+            let _ = fs::remove_file(&download_path);
+            println!("🎄 Input \"{input_path}\" is unchanged.");
+        }
+        Some(existing)
+            if !overwrite && check_placeholder(&String::from_utf8_lossy(&existing)).is_none() =>
+        {
+            let _ = fs::remove_file(&download_path);
+            eprintln!("🎄 Input \"{input_path}\" differs from the download and was kept. Run `cargo download {day} --overwrite{}` to replace it.", profile::get_profile_flag());
+        }
+        _ => {
+            if let Err(e) = fs::rename(&download_path, &input_path) {
+                eprintln!("failed to write input: {e}");
+                process::exit(1);
+            }
+            println!("🎄 Successfully wrote input to \"{input_path}\".");
+        }
+    }
+
+    println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+}
+
+fn store_meta(meta: &DownloadMeta) {
+    if let Err(e) = meta.store_file() {
+        eprintln!("Failed to store download metadata: {e}");
+    }
 }
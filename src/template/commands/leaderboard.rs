@@ -0,0 +1,159 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    time::{Duration, SystemTime},
+};
+
+use crate::template::leaderboard::{render_day, render_ranking, Leaderboard};
+use crate::template::{aoc_cli, profile, request_log, Day};
+
+/// The advent of code website asks to not request private leaderboards more often than every 15 minutes.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+fn get_cache_path(id: u64) -> PathBuf {
+    Path::new("data")
+        .join("leaderboard")
+        .join(format!("{id}.json"))
+}
+
+fn is_fresh(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < MIN_REFRESH_INTERVAL)
+}
+
+/// Quote a value for a curl config file.
+fn quote_config_value(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/// aoc-cli only prints leaderboards as tables, so the JSON is fetched with curl. The session cookie and User-Agent
+/// are passed in a config on stdin (`-K -`), so the session does not show up in the process list.
+///
+/// The response is downloaded next to the cache and only replaces it once it parses, so an expired session (which
+/// gets redirected to a login page) does not overwrite a good leaderboard.
+fn fetch(id: u64, path: &Path) -> Result<(), String> {
+    let year = aoc_cli::get_year().ok_or("`AOC_YEAR` is not set.")?;
+    let session = profile::get_session().ok_or("could not find a session cookie.")?;
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let download_path = path.with_extension("json.download");
+
+    request_log::throttle("leaderboard");
+
+    let mut cmd = Command::new("curl")
+        .args(["--silent", "--fail", "--config", "-", "--output"])
+        .arg(&download_path)
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|_| "command \"curl\" not found or not callable.")?;
+
+    let config = format!(
+        "user-agent = {}\ncookie = {}\n",
+        quote_config_value(&request_log::get_user_agent()),
+        quote_config_value(&format!("session={session}"))
+    );
+    // stdin is dropped before waiting, so curl sees the end of the config.
+    let written = match cmd.stdin.take() {
+        Some(mut stdin) => stdin.write_all(config.as_bytes()),
+        None => Ok(()),
+    };
+
+    let status = cmd.wait().map_err(|e| e.to_string())?;
+    written.map_err(|e| e.to_string())?;
+
+    if !status.success() {
+        let _ = fs::remove_file(&download_path);
+        return Err(format!("failed to fetch \"{url}\"."));
+    }
+
+    let parsed = fs::read_to_string(&download_path)
+        .map_err(|e| e.to_string())
+        .and_then(Leaderboard::try_from);
+
+    if let Err(e) = parsed {
+        let _ = fs::remove_file(&download_path);
+        return Err(format!(
+            "\"{url}\" did not return a leaderboard: {e} Your session cookie may have expired."
+        ));
+    }
+
+    fs::rename(&download_path, path).map_err(|e| e.to_string())?;
+    println!("🎄 Saved leaderboard to \"{}\".", path.display());
+    Ok(())
+}
+
+pub fn handle(file: Option<String>, id: Option<u64>, day: Option<Day>, refresh: bool) {
+    let id = id.or_else(|| env::var("AOC_LEADERBOARD_ID").ok()?.parse().ok());
+
+    let path = match (file, id) {
+        (Some(file), _) => PathBuf::from(file),
+        (None, Some(id)) => {
+            let path = get_cache_path(id);
+            let needs_fetch = !path.exists() || (refresh && !is_fresh(&path));
+
+            if refresh && !needs_fetch {
+                eprintln!("Cached leaderboard is less than 15 minutes old, skipping refresh.");
+            }
+
+            if needs_fetch {
+                if let Err(e) = fetch(id, &path) {
+                    eprintln!("Failed to fetch leaderboard: {e}");
+                    process::exit(1);
+                }
+            }
+
+            path
+        }
+        (None, None) => {
+            eprintln!("Pass a leaderboard with `--file <path>` or `--id <id>`, or set `AOC_LEADERBOARD_ID`.");
+            process::exit(1);
+        }
+    };
+
+    let leaderboard = match fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(Leaderboard::try_from)
+    {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Failed to read leaderboard \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    };
+
+    match day {
+        Some(day) => println!("{}", render_day(&leaderboard, day)),
+        None => println!("{}", render_ranking(&leaderboard)),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::quote_config_value;
+
+    #[test]
+    fn quotes_config_values() {
+        assert_eq!(
+            quote_config_value("session=53616c74"),
+            "\"session=53616c74\""
+        );
+        assert_eq!(
+            quote_config_value(r#"me "at" C:\aoc"#),
+            r#""me \"at\" C:\\aoc""#
+        );
+    }
+}
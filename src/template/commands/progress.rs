@@ -0,0 +1,20 @@
+use crate::template::progress::{render_calendar, Progress};
+use crate::template::{aoc_cli, readme_stars};
+
+pub fn handle(store: bool) {
+    let progress = Progress::collect();
+
+    println!("{}", render_calendar(&progress));
+
+    if store {
+        println!();
+        match readme_stars::update(&progress, aoc_cli::get_year()) {
+            Ok(()) => {
+                println!("Stored updated stars.");
+            }
+            Err(_) => {
+                eprintln!("Failed to store updated stars.");
+            }
+        }
+    }
+}
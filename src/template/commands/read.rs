@@ -1,15 +1,91 @@
-use std::process;
+use std::{
+    env, fs,
+    io::{stdout, IsTerminal, Write},
+    process::{self, Command, Stdio},
+};
 
+use crate::template::markdown::{parse, render, select_code, select_part};
 use crate::template::{aoc_cli, Day};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+const DEFAULT_SIZE: (usize, usize) = (80, 24);
+const MAX_WIDTH: usize = 100;
+
+/// Render the cached puzzle description of a day in the terminal.
+pub fn handle(day: Day, part: Option<u8>, code_only: bool) {
+    let path = aoc_cli::get_puzzle_path(day);
+
+    let Ok(markdown) = fs::read_to_string(&path) else {
+        eprintln!("Could not read \"{path}\". Run `cargo download {day}` to download the puzzle description.");
         process::exit(1);
+    };
+
+    let mut blocks = parse(&markdown);
+
+    if let Some(part) = part {
+        if !day.has_part(part) {
+            eprintln!("Day {day} does not have a part {part}.");
+            process::exit(1);
+        }
+        blocks = select_part(blocks, part);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    if code_only {
+        blocks = select_code(blocks);
+    }
+
+    let (width, height) = terminal_size();
+    let rendered = render(&blocks, width.min(MAX_WIDTH));
+
+    if stdout().is_terminal() && rendered.lines().count() >= height && page(&rendered) {
+        return;
+    }
+
+    print!("{rendered}");
+}
+
+/// Size of the terminal in columns and rows, from `COLUMNS` / `LINES` or `stty`.
+fn terminal_size() -> (usize, usize) {
+    let from_env = |var: &str| env::var(var).ok().and_then(|v| v.parse().ok());
+
+    if let (Some(width), Some(height)) = (from_env("COLUMNS"), from_env("LINES")) {
+        return (width, height);
+    }
+
+    Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .and_then(|output| {
+            let output = String::from_utf8(output.stdout).ok()?;
+            let (rows, cols) = output.trim().split_once(' ')?;
+            Some((cols.parse().ok()?, rows.parse().ok()?))
+        })
+        .unwrap_or(DEFAULT_SIZE)
+}
+
+/// Show text in the pager from `PAGER`, or `less`. Returns false if no pager could be started.
+fn page(text: &str) -> bool {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+    let mut parts = pager.split_whitespace();
+
+    let Some(program) = parts.next() else {
+        return false;
+    };
+
+    let Ok(mut child) = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        return false;
     };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager may exit before reading all input.
+        let _ = stdin.write_all(text.as_bytes());
+    }
+
+    child.wait().is_ok()
 }
@@ -1,8 +1,23 @@
-use std::process::{Command, Stdio};
+use std::{
+    fs,
+    process::{Command, Stdio},
+};
 
-use crate::template::Day;
+use crate::template::answers::Answers;
+use crate::template::bench_config::BenchConfig;
+use crate::template::heap_profile::{get_path_for_profile, render_summary, HeapProfile};
+use crate::template::run_multi::child_commands;
+use crate::template::timings::Timings;
+use crate::template::{profile, Day, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    count_allocs: bool,
+    submit_part: Option<u8>,
+    solution_args: &[String],
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,10 +27,21 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
+
+        // remove stale profiles, so parts that did not run are not summarized.
+        for &part in day.parts() {
+            let _ = fs::remove_file(get_path_for_profile(day, part));
+        }
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    // dhat installs its own allocator, which takes precedence.
+    if count_allocs && !dhat {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("count-allocs".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +49,8 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args.extend_from_slice(solution_args);
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -31,4 +59,137 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .unwrap();
 
     cmd.wait().unwrap();
+
+    if dhat {
+        summarize_heap_profiles(day);
+    }
+}
+
+/// Print a summary of the heap profile of each part and store the peak usage with the timings of the default inputs.
+fn summarize_heap_profiles(day: Day) {
+    let mut peak_bytes = None;
+
+    println!();
+
+    for &part in day.parts() {
+        let path = get_path_for_profile(day, part);
+
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        match HeapProfile::try_from(contents) {
+            Ok(profile) => {
+                println!("{}", render_summary(part, &profile));
+                peak_bytes = peak_bytes.max(Some(profile.peak_bytes));
+            }
+            Err(e) => {
+                eprintln!("Failed to read heap profile \"{path}\": {e}");
+            }
+        }
+    }
+
+    // like benchmarks, the peak memory is only stored for the default inputs.
+    if profile::get_profile().is_some() {
+        return;
+    }
+
+    if let Some(peak_bytes) = peak_bytes {
+        let mut timings = Timings::read_from_file();
+        timings.set_peak_bytes(day, peak_bytes);
+        match timings.store_file() {
+            Ok(()) => {
+                println!("\nStored peak memory, run `cargo time --store` to update the readme.")
+            }
+            Err(e) => eprintln!("Failed to store peak memory: {e}"),
+        }
+    }
+}
+
+/// Run a day against the input of every profile and tabulate the answers.
+pub fn handle_profiles(day: Day, release: bool) {
+    let mut rows = vec![vec![
+        "Profile".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+    ]];
+
+    for name in profile::list_profiles() {
+        if let Err(e) = profile::set_profile(&name) {
+            eprintln!("Skipping profile: {e}");
+            continue;
+        }
+
+        if !profile::get_input_path(day).exists() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Profile {name}{ANSI_RESET}");
+        println!("------");
+
+        let output =
+            match child_commands::run_solution(day, false, release, false, &BenchConfig::default())
+            {
+                Ok(output) => output,
+                Err(e) => {
+                    eprintln!("Failed to run day {day} for profile {name}: {e:?}");
+                    continue;
+                }
+            };
+        println!();
+
+        let mut row = vec![name];
+
+        if child_commands::has_no_input(&output) {
+            row.extend(["no input".to_string(), String::new()]);
+        } else {
+            // the runner stores the answers of each profile.
+            let answers = Answers::read_from_file();
+
+            for part in 1..=2 {
+                let cell = if !day.has_part(part) {
+                    String::new()
+                } else if child_commands::is_part_solved(&output, part) {
+                    answers
+                        .get(day, part)
+                        .map_or("?".into(), |a| a.answer.clone())
+                } else {
+                    "✖".into()
+                };
+                row.push(cell);
+            }
+        }
+
+        rows.push(row);
+    }
+
+    if rows.len() == 1 {
+        println!("No inputs found for day {day}.");
+        return;
+    }
+
+    println!("{ANSI_BOLD}Day {day} answers{ANSI_RESET}");
+    print!("{}", render_table(&rows));
+}
+
+fn render_table(rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            format!("{}\n", cells.join("  ").trim_end())
+        })
+        .collect()
 }
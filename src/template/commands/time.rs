@@ -1,10 +1,23 @@
 use std::collections::HashSet;
 
+use crate::template::bench_config::BenchConfig;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, profile, readme_benchmarks, Day};
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    count_allocs: bool,
+    bench_config: &BenchConfig,
+) {
+    // the stored timings and the readme benchmarks are those of the default inputs.
+    if store && profile::get_profile().is_some() {
+        eprintln!("`--store` cannot be combined with `--profile`, benchmarks are only stored for the default inputs.");
+        std::process::exit(1);
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,7 +34,7 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, count_allocs, bench_config).unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -0,0 +1,23 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::Day;
+
+pub fn handle(day: Day) {
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--bin",
+            &day.to_string(),
+            "--",
+            "--validate",
+        ])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(1);
+    }
+}
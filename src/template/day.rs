@@ -41,8 +41,27 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Returns the puzzle parts that exist for this day.
+    ///
+    /// Day 25 only has a single puzzle, its second star is awarded for collecting all other stars.
+    pub fn parts(self) -> &'static [u8] {
+        if self.0 == 25 {
+            &[1]
+        } else {
+            &[1, 2]
+        }
+    }
+
+    /// Returns `true` if the given part exists for this day.
+    pub fn has_part(self, part: u8) -> bool {
+        self.parts().contains(&part)
+    }
 }
 
+/// The number of stars that can be collected over the whole event.
+pub const MAX_STARS: usize = 50;
+
 #[cfg(feature = "today")]
 impl Day {
     /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
@@ -154,7 +173,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, MAX_STARS};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +206,21 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn day_parts() {
+        assert_eq!(Day(1).parts(), &[1, 2]);
+        assert_eq!(Day(24).parts(), &[1, 2]);
+        assert_eq!(Day(25).parts(), &[1]);
+        assert!(!Day(25).has_part(2));
+    }
+
+    #[test]
+    fn max_stars_matches_parts() {
+        // every part is worth a star, plus the free second star of day 25.
+        let stars: usize = all_days().map(|day| day.parts().len()).sum();
+        assert_eq!(stars + 1, MAX_STARS);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,249 @@
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{profile, Day};
+
+#[must_use]
+pub fn sha256_hex(contents: &[u8]) -> String {
+    Sha256::digest(contents)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Size and checksum of a downloaded file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileMeta {
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+impl FileMeta {
+    pub fn new(contents: &[u8]) -> Self {
+        Self {
+            bytes: contents.len() as u64,
+            sha256: sha256_hex(contents),
+        }
+    }
+
+    pub fn matches(&self, contents: &[u8]) -> bool {
+        self.bytes == contents.len() as u64 && self.sha256 == sha256_hex(contents)
+    }
+}
+
+/// Provenance of the input and puzzle description of a day, as of their last download.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DownloadMeta {
+    pub day: Day,
+    /// Seconds since the unix epoch.
+    pub downloaded_at: u64,
+    /// The session profile that was used for the download.
+    pub profile: String,
+    /// A short checksum of the session cookie, to tell accounts apart without storing the cookie.
+    pub session: Option<String>,
+    pub input: FileMeta,
+    pub puzzle: Option<FileMeta>,
+}
+
+impl DownloadMeta {
+    pub fn new(day: Day, profile: &str, session: Option<&str>, input: &[u8]) -> Self {
+        Self {
+            day,
+            downloaded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            profile: profile.into(),
+            session: session.map(|s| sha256_hex(s.as_bytes())[..12].to_string()),
+            input: FileMeta::new(input),
+            puzzle: None,
+        }
+    }
+
+    /// Read the metadata of a day for the active profile.
+    /// Returns `None` if the day was not downloaded or the file is invalid.
+    pub fn read_from_file(day: Day) -> Option<Self> {
+        fs::read_to_string(profile::get_meta_path(day))
+            .ok()
+            .and_then(|s| DownloadMeta::try_from(s).ok())
+    }
+
+    pub fn store_file(&self) -> Result<(), Error> {
+        let path = profile::get_meta_path(self.day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+}
+
+/// Report an input that no longer matches its download, e.g. because it was edited by hand.
+pub fn check_download(day: Day, input: &str) -> Option<String> {
+    let meta = DownloadMeta::read_from_file(day)?;
+
+    if meta.input.matches(input.as_bytes()) {
+        None
+    } else {
+        Some(format!(
+            "{} was changed after it was downloaded ({} bytes, downloaded {} bytes)",
+            profile::get_input_path(day).display(),
+            input.len(),
+            meta.input.bytes
+        ))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&FileMeta> for JsonValue {
+    fn from(value: &FileMeta) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert("sha256".into(), JsonValue::String(value.sha256.clone()));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for FileMeta {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected file metadata to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let bytes = json
+            .get("bytes")
+            .and_then(|v| v.get::<f64>())
+            .map(|bytes| *bytes as u64)
+            .ok_or("Expected file.bytes to be a number.")?;
+
+        let sha256 = json
+            .get("sha256")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected file.sha256 to be a string.")?;
+
+        Ok(FileMeta {
+            bytes,
+            sha256: sha256.clone(),
+        })
+    }
+}
+
+impl From<&DownloadMeta> for JsonValue {
+    fn from(value: &DownloadMeta) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "downloaded_at".into(),
+            JsonValue::Number(value.downloaded_at as f64),
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "session".into(),
+            match &value.session {
+                Some(session) => JsonValue::String(session.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("input".into(), JsonValue::from(&value.input));
+        map.insert(
+            "puzzle".into(),
+            match &value.puzzle {
+                Some(puzzle) => JsonValue::from(puzzle),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for DownloadMeta {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected metadata to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected meta.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let downloaded_at = json
+            .get("downloaded_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|ts| *ts as u64)
+            .ok_or("Expected meta.downloaded_at to be a number.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected meta.profile to be a string.")?;
+
+        let session = json.get("session").and_then(|v| v.get::<String>()).cloned();
+
+        let input = FileMeta::try_from(json.get("input").ok_or("Expected meta.input to exist.")?)?;
+
+        let puzzle = match json.get("puzzle") {
+            Some(v) if !v.is_null() => Some(FileMeta::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(DownloadMeta {
+            day,
+            downloaded_at,
+            profile: profile.clone(),
+            session,
+            input,
+            puzzle,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{sha256_hex, DownloadMeta, FileMeta};
+
+    #[test]
+    fn hashes_contents() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(FileMeta::new(b"1\n").matches(b"1\n"));
+        assert!(!FileMeta::new(b"1\n").matches(b"2\n"));
+    }
+
+    #[test]
+    fn roundtrips_meta() {
+        let mut meta = DownloadMeta::new(day!(5), "default", Some("secret"), b"1|2\n");
+        meta.puzzle = Some(FileMeta::new(b"# Day 5"));
+
+        let json = tinyjson::JsonValue::from(&meta).stringify().unwrap();
+        assert!(!json.contains("secret"));
+        assert_eq!(DownloadMeta::try_from(json).unwrap(), meta);
+    }
+}
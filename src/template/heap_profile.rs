@@ -0,0 +1,220 @@
+/// Module that summarizes the heap profiles written by dhat.
+/// For the file format, see: https://github.com/nnethercote/dhat-rs/blob/main/src/lib.rs
+use std::{collections::HashMap, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Number of allocation sites listed in a summary.
+const TOP_SITES: usize = 5;
+
+#[must_use]
+pub fn get_path_for_profile(day: Day, part: u8) -> String {
+    format!("dhat-heap-{day}-{part}.json")
+}
+
+/// Allocations made from a single call stack.
+#[derive(Clone, Debug)]
+pub struct AllocationSite {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    pub frames: Vec<String>,
+}
+
+impl AllocationSite {
+    /// The most relevant frame of the call stack: the innermost frame in solution code, if present.
+    pub fn location(&self) -> &str {
+        self.frames
+            .iter()
+            .find(|f| f.contains("src/bin/") || f.contains("src\\bin\\"))
+            .or_else(|| self.frames.iter().find(|f| !f.contains("[root]")))
+            .map_or("[unknown]", String::as_str)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HeapProfile {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    pub peak_bytes: u64,
+    pub sites: Vec<AllocationSite>,
+}
+
+impl HeapProfile {
+    /// Allocation sites, ordered by the amount of allocated bytes.
+    pub fn top_sites(&self) -> impl Iterator<Item = &AllocationSite> {
+        let mut sites: Vec<&AllocationSite> = self.sites.iter().collect();
+        sites.sort_by_key(|site| std::cmp::Reverse(site.total_bytes));
+        sites.into_iter().take(TOP_SITES)
+    }
+}
+
+/// Formats a byte count with a binary unit suffix.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes}B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1}{}", UNITS[unit])
+}
+
+pub fn render_summary(part: u8, profile: &HeapProfile) -> String {
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}Part {part}:{ANSI_RESET} {} total in {} allocations, {} peak",
+        format_bytes(profile.total_bytes),
+        profile.total_blocks,
+        format_bytes(profile.peak_bytes)
+    )];
+
+    for site in profile.top_sites() {
+        lines.push(format!(
+            "  {:>10} {:>8}x  {}",
+            format_bytes(site.total_bytes),
+            site.total_blocks,
+            site.location()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn get_u64(json: &HashMap<String, JsonValue>, key: &str) -> Option<u64> {
+    json.get(key)
+        .and_then(|v| v.get::<f64>())
+        .map(|v| *v as u64)
+}
+
+impl TryFrom<String> for HeapProfile {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let frame_table: Vec<&String> = json
+            .get("ftbl")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `json.ftbl` to be an array.")?
+            .iter()
+            .filter_map(|v| v.get::<String>())
+            .collect();
+
+        let program_points = json
+            .get("pps")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `json.pps` to be an array.")?;
+
+        let mut profile = HeapProfile {
+            total_bytes: 0,
+            total_blocks: 0,
+            peak_bytes: 0,
+            sites: Vec::with_capacity(program_points.len()),
+        };
+
+        for pp in program_points {
+            let pp = pp
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected program point to be an object.")?;
+
+            let total_bytes = get_u64(pp, "tb").ok_or("expected `pp.tb` to be a number.")?;
+            let total_blocks = get_u64(pp, "tbk").ok_or("expected `pp.tbk` to be a number.")?;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let frames = pp
+                .get("fs")
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .ok_or("expected `pp.fs` to be an array.")?
+                .iter()
+                .filter_map(|idx| idx.get::<f64>())
+                .filter_map(|idx| frame_table.get(*idx as usize))
+                .map(|frame| (*frame).clone())
+                .collect();
+
+            profile.total_bytes += total_bytes;
+            profile.total_blocks += total_blocks;
+            profile.peak_bytes += get_u64(pp, "gb").unwrap_or(0);
+            profile.sites.push(AllocationSite {
+                total_bytes,
+                total_blocks,
+                frames,
+            });
+        }
+
+        Ok(profile)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, HeapProfile};
+
+    fn get_mock_profile() -> HeapProfile {
+        let json = r#"{
+            "dhatFileVersion": 2, "mode": "rust-heap", "verb": "Allocated", "bklt": true, "bkacc": false,
+            "tu": "µs", "Mtu": "s", "tuth": 10, "cmd": "target/dhat/09", "pid": 1, "tg": 10, "te": 20,
+            "pps": [
+                { "tb": 4096, "tbk": 2, "tl": 5, "mb": 4096, "mbk": 1, "gb": 2048, "gbk": 1, "eb": 0, "ebk": 0, "fs": [1, 2] },
+                { "tb": 100, "tbk": 10, "tl": 5, "mb": 10, "mbk": 1, "gb": 10, "gbk": 1, "eb": 0, "ebk": 0, "fs": [1, 3] }
+            ],
+            "ftbl": [
+                "[root]",
+                "0x1: alloc::raw_vec::finish_grow (alloc/src/raw_vec.rs:1:1)",
+                "0x2: 09::part_two (src/bin/09.rs:95:5)",
+                "0x3: 09::part_one (src/bin/09.rs:35:5)"
+            ]
+        }"#;
+        HeapProfile::try_from(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn sums_program_points() {
+        let profile = get_mock_profile();
+        assert_eq!(profile.total_bytes, 4196);
+        assert_eq!(profile.total_blocks, 12);
+        assert_eq!(profile.peak_bytes, 2058);
+    }
+
+    #[test]
+    fn locates_solution_frames() {
+        let profile = get_mock_profile();
+        let sites: Vec<&str> = profile.top_sites().map(|s| s.location()).collect();
+        assert_eq!(
+            sites,
+            [
+                "0x2: 09::part_two (src/bin/09.rs:95:5)",
+                "0x3: 09::part_one (src/bin/09.rs:35:5)"
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_profiles() {
+        HeapProfile::try_from(r#"{ "pps": [] }"#.to_string()).unwrap();
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0MiB");
+    }
+}
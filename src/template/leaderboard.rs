@@ -0,0 +1,390 @@
+/// Module that parses and renders private leaderboards.
+/// The JSON format is documented on the private leaderboard page of the advent of code website.
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET};
+
+/// A single member of a private leaderboard.
+#[derive(Clone, Debug)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u64,
+    pub local_score: u64,
+    /// Unix timestamps of collected stars, keyed by `(day, part)`.
+    pub completions: BTreeMap<(Day, u8), i64>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star_ts(&self, day: Day, part: u8) -> Option<i64> {
+        self.completions.get(&(day, part)).copied()
+    }
+
+    fn last_star_ts(&self) -> i64 {
+        self.completions.values().copied().max().unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Leaderboard {
+    pub event: u16,
+    pub owner_id: u64,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// Recompute local scores: for every star, each member receives `n - rank` points,
+    /// where `n` is the number of members and `rank` the zero-based order in which the star was collected.
+    pub fn local_scores(&self) -> HashMap<u64, u64> {
+        let n = self.members.len() as u64;
+        let mut scores: HashMap<u64, u64> = self.members.iter().map(|m| (m.id, 0)).collect();
+
+        for day in all_days() {
+            for part in 1..=2 {
+                let mut finishers: Vec<(i64, u64)> = self
+                    .members
+                    .iter()
+                    .filter_map(|m| m.star_ts(day, part).map(|ts| (ts, m.id)))
+                    .collect();
+                finishers.sort_unstable();
+
+                for (rank, (_, id)) in finishers.iter().enumerate() {
+                    *scores.entry(*id).or_default() += n - rank as u64;
+                }
+            }
+        }
+
+        scores
+    }
+
+    /// Members ordered by recomputed local score, ties broken by who got their last star first.
+    pub fn ranked(&self) -> Vec<(&Member, u64)> {
+        let scores = self.local_scores();
+        let mut ranked: Vec<(&Member, u64)> = self
+            .members
+            .iter()
+            .map(|m| (m, scores.get(&m.id).copied().unwrap_or(0)))
+            .collect();
+
+        ranked.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .cmp(a_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts().cmp(&b.last_star_ts()))
+        });
+
+        ranked
+    }
+
+    /// Unix timestamp at which a day's puzzle unlocks (midnight EST).
+    pub fn unlock_ts(&self, day: Day) -> i64 {
+        days_from_civil(i64::from(self.event), 12, i64::from(day.into_inner())) * 86400 + 5 * 3600
+    }
+}
+
+/// Days since the unix epoch for a date of the proleptic gregorian calendar.
+/// See: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Formats a duration in seconds as `hh:mm:ss`, prefixed with days if longer than a day.
+pub fn format_delta(secs: i64) -> String {
+    let (days, rest) = (secs / 86400, secs % 86400);
+    let hms = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    );
+    if days > 0 {
+        format!("{days}d {hms}")
+    } else {
+        hms
+    }
+}
+
+/// Render the ranked members, along with a row of stars per member.
+pub fn render_ranking(leaderboard: &Leaderboard) -> String {
+    let mut lines = vec![
+        format!(
+            "{ANSI_BOLD}Private leaderboard {} (owner #{}){ANSI_RESET}",
+            leaderboard.event, leaderboard.owner_id
+        ),
+        String::new(),
+        format!(
+            "{:>3}  {:>5}  {:>5}  {:<25}  Name",
+            "#",
+            "Score",
+            "Stars",
+            (1..=25).map(|d| (d % 10).to_string()).collect::<String>()
+        ),
+    ];
+
+    for (rank, (member, score)) in leaderboard.ranked().iter().enumerate() {
+        let stars: String = all_days()
+            .map(|day| {
+                match (
+                    member.star_ts(day, 1).is_some(),
+                    member.star_ts(day, 2).is_some(),
+                ) {
+                    (true, true) => '★',
+                    (true, false) => '☆',
+                    _ => '·',
+                }
+            })
+            .collect();
+
+        let mut line = format!(
+            "{:>3}  {:>5}  {:>5}  {stars}  {}",
+            rank + 1,
+            score,
+            member.stars,
+            member.display_name()
+        );
+
+        if *score != member.local_score {
+            line.push_str(&format!(" (reported: {})", member.local_score));
+        }
+
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+/// Render star timestamps for a single day, relative to the puzzle unlock.
+pub fn render_day(leaderboard: &Leaderboard, day: Day) -> String {
+    let unlock = leaderboard.unlock_ts(day);
+    let format_ts = |ts: Option<i64>| ts.map_or_else(|| "-".into(), |ts| format_delta(ts - unlock));
+
+    let mut members: Vec<&Member> = leaderboard
+        .members
+        .iter()
+        .filter(|m| m.star_ts(day, 1).is_some())
+        .collect();
+    members.sort_by_key(|m| (m.star_ts(day, 2).unwrap_or(i64::MAX), m.star_ts(day, 1)));
+
+    let mut lines = vec![
+        format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"),
+        String::new(),
+        format!(
+            "{:>3}  {:>12}  {:>12}  {:>12}  Name",
+            "#", "Part 1", "Part 2", "Delta"
+        ),
+    ];
+
+    for (rank, member) in members.iter().enumerate() {
+        let part_1 = member.star_ts(day, 1);
+        let part_2 = member.star_ts(day, 2);
+        let delta = part_1.zip(part_2).map(|(a, b)| b - a);
+
+        lines.push(format!(
+            "{:>3}  {:>12}  {:>12}  {:>12}  {}",
+            rank + 1,
+            format_ts(part_1),
+            format_ts(part_2),
+            delta.map_or_else(|| "-".into(), format_delta),
+            member.display_name()
+        ));
+    }
+
+    if members.is_empty() {
+        lines.push("No stars collected yet.".into());
+    }
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn as_u64(value: &JsonValue) -> Option<u64> {
+    match value {
+        JsonValue::Number(n) => Some(*n as u64),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+impl TryFrom<String> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let event = json
+            .get("event")
+            .and_then(as_u64)
+            .map(|event| event as u16)
+            .ok_or("expected `json.event` to be a year.")?;
+
+        let owner_id = json
+            .get("owner_id")
+            .and_then(as_u64)
+            .ok_or("expected `json.owner_id` to be a number.")?;
+
+        let mut members: Vec<Member> = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.members` to be an object.")?
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<_, _>>()?;
+        members.sort_unstable_by_key(|m| m.id);
+
+        Ok(Leaderboard {
+            event,
+            owner_id,
+            members,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let id = json
+            .get("id")
+            .and_then(as_u64)
+            .ok_or("Expected member.id to be a number.")?;
+
+        let name = json.get("name").and_then(|v| v.get::<String>()).cloned();
+
+        let stars = json.get("stars").and_then(as_u64).unwrap_or(0);
+        let local_score = json.get("local_score").and_then(as_u64).unwrap_or(0);
+
+        let mut completions = BTreeMap::new();
+
+        let days = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?;
+
+        for (day, parts) in days {
+            let day = Day::from_str(day).map_err(|e| e.to_string())?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected completion day to be an object.")?;
+
+            for (part, star) in parts {
+                let part: u8 = part.parse().or(Err("Expected part to be a number."))?;
+                #[allow(clippy::cast_possible_wrap)]
+                let ts = star
+                    .get::<HashMap<String, JsonValue>>()
+                    .and_then(|star| star.get("get_star_ts"))
+                    .and_then(as_u64)
+                    .ok_or("Expected star.get_star_ts to be a number.")?
+                    as i64;
+                completions.insert((day, part), ts);
+            }
+        }
+
+        Ok(Member {
+            id,
+            name,
+            stars,
+            local_score,
+            completions,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_delta, Leaderboard};
+    use crate::day;
+
+    fn get_mock_leaderboard() -> Leaderboard {
+        let json = r#"{
+            "owner_id": 1,
+            "event": "2024",
+            "members": {
+                "1": {
+                    "id": 1, "name": "alice", "stars": 3, "local_score": 6, "global_score": 0,
+                    "last_star_ts": 1733030000,
+                    "completion_day_level": {
+                        "1": {
+                            "1": { "get_star_ts": 1733029500, "star_index": 1 },
+                            "2": { "get_star_ts": 1733030000, "star_index": 2 }
+                        },
+                        "2": { "1": { "get_star_ts": 1733116000, "star_index": 5 } }
+                    }
+                },
+                "2": {
+                    "id": 2, "name": null, "stars": 2, "local_score": 3, "global_score": 0,
+                    "last_star_ts": 1733029900,
+                    "completion_day_level": {
+                        "1": {
+                            "1": { "get_star_ts": 1733029400, "star_index": 0 },
+                            "2": { "get_star_ts": 1733029900, "star_index": 3 }
+                        }
+                    }
+                }
+            }
+        }"#;
+        Leaderboard::try_from(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(leaderboard.event, 2024);
+        assert_eq!(leaderboard.members.len(), 2);
+        assert_eq!(leaderboard.members[0].star_ts(day!(2), 1), Some(1733116000));
+        assert_eq!(leaderboard.members[1].display_name(), "(anonymous user #2)");
+    }
+
+    #[test]
+    fn recomputes_local_scores() {
+        let leaderboard = get_mock_leaderboard();
+        let scores = leaderboard.local_scores();
+        // day 1: the anonymous user is first for both parts. day 2: alice is the only finisher.
+        assert_eq!(scores[&1], 1 + 1 + 2);
+        assert_eq!(scores[&2], 2 + 2);
+        // ties are broken by stars.
+        assert_eq!(leaderboard.ranked()[0].0.id, 1);
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(leaderboard.unlock_ts(day!(1)), 1733029200);
+        assert_eq!(leaderboard.unlock_ts(day!(25)), 1733029200 + 24 * 86400);
+    }
+
+    #[test]
+    fn formats_deltas() {
+        assert_eq!(format_delta(59), "00:00:59");
+        assert_eq!(format_delta(3723), "01:02:03");
+        assert_eq!(format_delta(90000), "1d 01:00:00");
+    }
+}
@@ -0,0 +1,412 @@
+/// A small renderer for the puzzle descriptions that aoc-cli saves as markdown.
+/// It supports the subset that these files use: headings, paragraphs, lists, code blocks and inline emphasis.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const CODE_INDENT: &str = "    ";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Block {
+    Heading(String),
+    Paragraph(String),
+    ListItem(String),
+    Code(Vec<String>),
+}
+
+/// Parse markdown into blocks. Lines of paragraphs and list items are joined.
+pub fn parse(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut lines = markdown.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || is_rule(trimmed) {
+            continue;
+        }
+
+        if trimmed.starts_with("```") {
+            let mut code = vec![];
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with("```") {
+                    break;
+                }
+                code.push(line.to_string());
+            }
+            blocks.push(Block::Code(code));
+        } else if let Some(heading) = trimmed.strip_prefix('#') {
+            blocks.push(Block::Heading(
+                heading.trim_start_matches('#').trim().to_string(),
+            ));
+        } else if lines.peek().is_some_and(|next| is_underline(next.trim())) {
+            lines.next();
+            blocks.push(Block::Heading(trimmed.to_string()));
+        } else if let Some(item) = strip_bullet(trimmed) {
+            let mut text = item.to_string();
+            while let Some(next) = lines.peek() {
+                let next = next.trim();
+                if next.is_empty() || strip_bullet(next).is_some() || next.starts_with("```") {
+                    break;
+                }
+                text.push(' ');
+                text.push_str(next);
+                lines.next();
+            }
+            blocks.push(Block::ListItem(text));
+        } else {
+            let mut text = trimmed.to_string();
+            while let Some(next) = lines.peek() {
+                let next = next.trim();
+                if next.is_empty()
+                    || next.starts_with("```")
+                    || next.starts_with('#')
+                    || strip_bullet(next).is_some()
+                {
+                    break;
+                }
+                text.push(' ');
+                text.push_str(next);
+                lines.next();
+            }
+            blocks.push(Block::Paragraph(text));
+        }
+    }
+
+    blocks
+}
+
+fn is_rule(line: &str) -> bool {
+    line.len() >= 3 && (line.chars().all(|c| c == '*') || line.chars().all(|c| c == '-'))
+}
+
+fn is_underline(line: &str) -> bool {
+    !line.is_empty() && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+fn strip_bullet(line: &str) -> Option<&str> {
+    line.strip_prefix("* ")
+        .or_else(|| line.strip_prefix("- "))
+        .or_else(|| line.strip_prefix("+ "))
+}
+
+/// Keep the blocks of one part. Part two starts at the heading that contains "Part Two".
+pub fn select_part(blocks: Vec<Block>, part: u8) -> Vec<Block> {
+    let start = blocks
+        .iter()
+        .position(|block| matches!(block, Block::Heading(h) if h.contains("Part Two")))
+        .unwrap_or(blocks.len());
+
+    let mut blocks = blocks;
+    match part {
+        1 => {
+            blocks.truncate(start);
+            blocks
+        }
+        _ => blocks.split_off(start),
+    }
+}
+
+/// Keep only the code blocks.
+pub fn select_code(blocks: Vec<Block>) -> Vec<Block> {
+    blocks
+        .into_iter()
+        .filter(|block| matches!(block, Block::Code(_)))
+        .collect()
+}
+
+/// Render blocks for the terminal, wrapping text to `width` columns. Code blocks are indented and not wrapped.
+pub fn render(blocks: &[Block], width: usize) -> String {
+    let mut out = String::new();
+
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+
+        match block {
+            Block::Heading(text) => {
+                let text = unescape(text);
+                out.push_str(&format!("{ANSI_BOLD}{}{ANSI_RESET}\n", text.trim()));
+            }
+            Block::Paragraph(text) => {
+                for line in wrap(&render_inline(text), width, "", "") {
+                    out.push_str(&line);
+                    out.push('\n');
+                }
+            }
+            Block::ListItem(text) => {
+                for line in wrap(&render_inline(text), width, "  • ", "    ") {
+                    out.push_str(&line);
+                    out.push('\n');
+                }
+            }
+            Block::Code(lines) => {
+                for line in lines {
+                    out.push_str(CODE_INDENT);
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
+    out
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    bold: bool,
+    italic: bool,
+}
+
+impl Style {
+    fn apply(self, text: &str) -> String {
+        if self == Style::default() {
+            return text.to_string();
+        }
+
+        let mut styled = String::new();
+        if self.bold {
+            styled.push_str(ANSI_BOLD);
+        }
+        if self.italic {
+            styled.push_str(ANSI_ITALIC);
+        }
+        styled.push_str(text);
+        styled.push_str(ANSI_RESET);
+        styled
+    }
+}
+
+/// A word made of styled runs of text.
+#[derive(Default)]
+struct Word {
+    runs: Vec<(Style, String)>,
+    /// The number of columns the word takes up, which excludes escape codes.
+    width: usize,
+}
+
+impl Word {
+    fn push(&mut self, c: char, style: Style) {
+        match self.runs.last_mut() {
+            Some((last_style, text)) if *last_style == style => text.push(c),
+            _ => self.runs.push((style, c.to_string())),
+        }
+        self.width += 1;
+    }
+
+    fn push_str(&mut self, s: &str, style: Style) {
+        s.chars().for_each(|c| self.push(c, style));
+    }
+
+    fn render(&self) -> String {
+        self.runs
+            .iter()
+            .map(|(style, text)| style.apply(text))
+            .collect()
+    }
+}
+
+/// Split text into styled words. `**strong**` is bold and `*emphasis*` is italic. Code spans are kept verbatim,
+/// unless the whole span is emphasized, which is how the puzzles highlight values.
+fn render_inline(text: &str) -> Vec<Word> {
+    let mut words = vec![];
+    let mut word = Word::default();
+    let mut style = Style::default();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' => {
+                if word.width > 0 {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    word.push(escaped, style);
+                }
+            }
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                style.bold = !style.bold;
+            }
+            '*' => style.italic = !style.italic,
+            '`' => {
+                let code: String = chars.by_ref().take_while(|&c| c != '`').collect();
+
+                if code.len() > 2 && code.starts_with('*') && code.ends_with('*') {
+                    let emphasized = Style {
+                        italic: true,
+                        ..style
+                    };
+                    word.push_str(code.trim_matches('*'), emphasized);
+                } else {
+                    word.push_str(&code, style);
+                }
+            }
+            '[' => {
+                // links are rendered as their text, other brackets as they are.
+                let rest: String = chars.clone().collect();
+                match parse_link(&rest) {
+                    Some((label, len)) => {
+                        word.push_str(label, style);
+                        chars.by_ref().take(len).for_each(drop);
+                    }
+                    None => word.push(c, style),
+                }
+            }
+            c => word.push(c, style),
+        }
+    }
+
+    if word.width > 0 {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Parse a link `label](url)` at the start of `rest`, which follows an opening bracket. Returns the label and the
+/// number of chars up to and including the closing parenthesis.
+fn parse_link(rest: &str) -> Option<(&str, usize)> {
+    let mut depth = 1;
+    let (close, _) = rest.char_indices().find(|&(_, c)| {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => {}
+        }
+        depth == 0
+    })?;
+
+    let label = &rest[..close];
+    let url = rest[close + 1..].strip_prefix('(')?.split_once(')')?.0;
+    Some((label, label.chars().count() + url.chars().count() + 3))
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.extend(chars.next()),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Wrap words into lines of at most `width` columns. Words longer than a line are not split.
+fn wrap(words: &[Word], width: usize, first_indent: &str, indent: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_indent.to_string();
+    let mut line_width = first_indent.chars().count();
+    let mut is_empty = true;
+
+    for word in words {
+        if !is_empty && line_width + 1 + word.width > width {
+            lines.push(std::mem::replace(&mut line, indent.to_string()));
+            line_width = indent.chars().count();
+            is_empty = true;
+        }
+
+        if !is_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        line.push_str(&word.render());
+        line_width += word.width;
+        is_empty = false;
+    }
+
+    if !is_empty {
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+The list on the *left* and the list on the [right](https://example.com).
+
+```
+3   4
+4   3
+```
+
+* Pair up the *smallest number* in the left list.
+* Add up all of the distances.
+
+Your puzzle answer was `1234`.
+
+\--- Part Two ---
+----------
+
+In the example list above, the similarity score is `*31*` (`3 + 4`).
+";
+
+    #[test]
+    fn parses_blocks() {
+        let blocks = parse(PUZZLE);
+        assert_eq!(
+            blocks[0],
+            Block::Heading(r"\--- Day 1: Historian Hysteria ---".into())
+        );
+        assert_eq!(blocks[2], Block::Code(vec!["3   4".into(), "4   3".into()]));
+        assert_eq!(
+            blocks[3],
+            Block::ListItem("Pair up the *smallest number* in the left list.".into())
+        );
+        assert_eq!(blocks.len(), 8);
+    }
+
+    #[test]
+    fn selects_parts() {
+        let part_one = select_part(parse(PUZZLE), 1);
+        assert_eq!(part_one.len(), 6);
+        let part_two = select_part(parse(PUZZLE), 2);
+        assert_eq!(part_two[0], Block::Heading(r"\--- Part Two ---".into()));
+        let code = select_code(parse(PUZZLE));
+        assert_eq!(code.len(), 1);
+    }
+
+    #[test]
+    fn renders_inline_styles() {
+        let rendered = render(&parse(PUZZLE), 80);
+        assert!(rendered.starts_with(&format!(
+            "{ANSI_BOLD}--- Day 1: Historian Hysteria ---{ANSI_RESET}\n"
+        )));
+        assert!(rendered.contains(&format!("{ANSI_ITALIC}left{ANSI_RESET}")));
+        assert!(rendered.contains("list on the right."));
+        assert!(rendered.contains("\n    3   4\n"));
+        assert!(rendered.contains("(3 + 4)."));
+        assert!(rendered.contains(&format!("is {ANSI_ITALIC}31{ANSI_RESET} (3 + 4).")));
+    }
+
+    #[test]
+    fn renders_stray_brackets() {
+        let text = "Use a[i] or [the docs](https://example.com), not [this] (one).";
+        let rendered = render(&[Block::Paragraph(text.into())], 80);
+        assert_eq!(rendered, "Use a[i] or the docs, not [this] (one).\n");
+        let rendered = render(
+            &[Block::Paragraph("[[Open]](https://example.com) [".into())],
+            80,
+        );
+        assert_eq!(rendered, "[Open] [\n");
+    }
+
+    #[test]
+    fn wraps_to_width() {
+        let rendered = render(&[Block::ListItem("aaa bbb ccc ddd".into())], 12);
+        assert_eq!(rendered, "  • aaa bbb\n    ccc ddd\n");
+        let rendered = render(&[Block::Paragraph("aaaaaaaaaaaa b".into())], 5);
+        assert_eq!(rendered, "aaaaaaaaaaaa\nb\n");
+    }
+}
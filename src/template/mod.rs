@@ -1,15 +1,29 @@
 use std::{env, fs};
 
+pub mod alloc_counter;
 pub mod aoc_cli;
+pub mod bench_config;
 pub mod commands;
+pub mod profile;
 pub mod runner;
 
 pub use day::*;
+pub use solution_error::*;
 
+mod answers;
 mod day;
+mod download_meta;
+mod heap_profile;
+mod leaderboard;
+mod markdown;
+mod progress;
 mod readme_benchmarks;
+mod readme_stars;
+mod request_log;
 mod run_multi;
+mod solution_error;
 mod timings;
+mod validate;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -39,19 +53,45 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternatively, pass `setup = <fn>` to prepare the input of each part from the puzzle input, e.g. to parse it
+/// into an owned structure that `part_one` and `part_two` take by value. The setup is excluded from benchmarks.
+///
+/// Pass `validate = <fn>` to check the structure of the puzzle input before solving, e.g. with
+/// `fn validate(input: &str) -> Result<(), impl Display>`. It also runs for `cargo validate <day>`.
+///
+/// Pass `reset = <fn>` to clear a global cache (e.g. of a `#[cached]` function) before every part, and before every
+/// benchmark sample with `cargo time --reset-caches`. Options can be combined in any order.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, ::std::convert::identity, $crate::template::runner::no_validation, [], [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, ::std::convert::identity, $crate::template::runner::no_validation, [], [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, ::std::convert::identity, $crate::template::runner::no_validation, [], [part_two, 2]);
+    };
+    ($day:expr, $($key:ident = $value:expr),+ $(,)?) => {
+        $crate::solution!(@options $day, [::std::convert::identity] [$crate::template::runner::no_validation] [], $($key = $value),+);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    // options can be given in any order, each one replaces its default.
+    (@options $day:expr, [$setup:expr] [$validate:expr] [$($reset:expr)?], setup = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@options $day, [$value] [$validate] [$($reset)?] $(, $($rest)*)?);
+    };
+    (@options $day:expr, [$setup:expr] [$validate:expr] [$($reset:expr)?], validate = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@options $day, [$setup] [$value] [$($reset)?] $(, $($rest)*)?);
+    };
+    (@options $day:expr, [$setup:expr] [$validate:expr] [$($reset:expr)?], reset = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@options $day, [$setup] [$validate] [$value] $(, $($rest)*)?);
+    };
+    (@options $day:expr, [$setup:expr] [$validate:expr] [$($reset:expr)?] $(,)?) => {
+        $crate::solution!(@impl $day, $setup, $validate, [$($reset)?], [part_one, 1] [part_two, 2]);
+    };
+
+    (@impl $day:expr, $setup:expr, $validate:expr, [$($reset:expr)?], $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +99,17 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc_counter::CountingAlloc =
+            $crate::template::alloc_counter::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $( $crate::memo::register_reset($reset); )?
+            let input = read_input(DAY);
+            validate_input(&input, DAY, $validate);
+            $( run_part_with_setup(|| $setup(input.as_str()), $func, DAY, $part); )*
         }
     };
 }
@@ -0,0 +1,139 @@
+/// Input profiles allow keeping the inputs of several advent of code accounts in one repository.
+/// The default profile uses `data/inputs/DD.txt`, a named profile uses `data/inputs/<profile>/DD.txt`.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::Day;
+
+/// The profile that is used when no other profile is selected.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Environment variable that selects the active profile, mirrored to solution binaries.
+const PROFILE_ENV: &str = "AOC_PROFILE";
+
+/// The active profile, `None` for the default profile.
+#[must_use]
+pub fn get_profile() -> Option<String> {
+    env::var(PROFILE_ENV)
+        .ok()
+        .filter(|profile| !profile.is_empty() && profile != DEFAULT_PROFILE)
+}
+
+#[must_use]
+pub fn get_profile_name() -> String {
+    get_profile().unwrap_or_else(|| DEFAULT_PROFILE.into())
+}
+
+/// The `--profile` flag that selects the active profile, for commands that are suggested to the user.
+#[must_use]
+pub fn get_profile_flag() -> String {
+    get_profile()
+        .map(|profile| format!(" --profile {profile}"))
+        .unwrap_or_default()
+}
+
+/// Select the active profile for this process and the solution binaries it runs.
+pub fn set_profile(profile: &str) -> Result<(), String> {
+    if profile.is_empty()
+        || !profile
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "invalid profile name `{profile}`, use letters, digits, `-` and `_`."
+        ));
+    }
+
+    env::set_var(PROFILE_ENV, profile);
+    Ok(())
+}
+
+/// All profiles that have inputs, starting with the default profile.
+#[must_use]
+pub fn list_profiles() -> Vec<String> {
+    let mut profiles: Vec<String> = fs::read_dir(Path::new("data").join("inputs"))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name != DEFAULT_PROFILE)
+        .collect();
+
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.into());
+    profiles
+}
+
+fn get_profile_dir(folder: &str) -> PathBuf {
+    let dir = Path::new("data").join(folder);
+    match get_profile() {
+        Some(profile) => dir.join(profile),
+        None => dir,
+    }
+}
+
+#[must_use]
+pub fn get_input_path(day: Day) -> PathBuf {
+    get_profile_dir("inputs").join(format!("{day}.txt"))
+}
+
+#[must_use]
+pub fn get_meta_path(day: Day) -> PathBuf {
+    get_profile_dir("meta").join(format!("{day}.json"))
+}
+
+#[must_use]
+pub fn get_answers_path() -> PathBuf {
+    match get_profile() {
+        Some(profile) => Path::new("data")
+            .join("answers")
+            .join(format!("{profile}.json")),
+        None => Path::new("data").join("answers.json"),
+    }
+}
+
+/// Reads the session cookie of the active profile.
+/// The default profile reads it the same way aoc-cli does, from `ADVENT_OF_CODE_SESSION` or
+/// `~/.adventofcode.session`. A named profile reads it from `ADVENT_OF_CODE_SESSION_<PROFILE>` or
+/// `~/.adventofcode.<profile>.session`.
+#[must_use]
+pub fn get_session() -> Option<String> {
+    let profile = get_profile();
+
+    let (var, file) = match &profile {
+        Some(profile) => (
+            format!(
+                "ADVENT_OF_CODE_SESSION_{}",
+                profile.to_uppercase().replace('-', "_")
+            ),
+            format!(".adventofcode.{profile}.session"),
+        ),
+        None => (
+            "ADVENT_OF_CODE_SESSION".into(),
+            ".adventofcode.session".into(),
+        ),
+    };
+
+    if let Ok(session) = env::var(var) {
+        return Some(session.trim().to_string());
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    fs::read_to_string(Path::new(&home).join(file))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::set_profile;
+
+    #[test]
+    fn rejects_invalid_names() {
+        assert!(set_profile("../alice").is_err());
+        assert!(set_profile("").is_err());
+    }
+}
@@ -0,0 +1,235 @@
+/// Module that combines local signals into a progress overview per day.
+use std::path::Path;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET};
+
+/// Status of a single part, ordered by how far along it is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PartStatus {
+    /// The part does not exist for this day, or its star has not been awarded yet (day 25).
+    Unavailable,
+    /// No solution has been scaffolded yet.
+    Missing,
+    Scaffolded,
+    /// An answer was computed, but neither submitted nor benched.
+    Answered,
+    /// The last submitted answer was rejected.
+    Rejected,
+    /// The answer was accepted or the part has been benched.
+    Solved,
+}
+
+impl PartStatus {
+    fn symbol(self) -> char {
+        match self {
+            PartStatus::Unavailable | PartStatus::Missing => ' ',
+            PartStatus::Scaffolded => '·',
+            PartStatus::Answered => '☆',
+            PartStatus::Rejected => '✖',
+            PartStatus::Solved => '★',
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DayProgress {
+    pub day: Day,
+    pub part_1: PartStatus,
+    pub part_2: PartStatus,
+}
+
+impl DayProgress {
+    pub fn part(&self, part: u8) -> PartStatus {
+        match part {
+            1 => self.part_1,
+            2 => self.part_2,
+            _ => PartStatus::Unavailable,
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        [self.part_1, self.part_2]
+            .iter()
+            .filter(|&&status| status == PartStatus::Solved)
+            .count()
+    }
+
+    /// A day is complete once every part that exists for it is solved.
+    pub fn is_complete(&self) -> bool {
+        self.day
+            .parts()
+            .iter()
+            .all(|&part| self.part(part) == PartStatus::Solved)
+    }
+}
+
+/// Progress for every day of advent.
+#[derive(Clone, Debug)]
+pub struct Progress {
+    pub days: Vec<DayProgress>,
+}
+
+impl Progress {
+    /// Collect progress from the local repository: scaffolded bins, stored answers and benchmarks.
+    pub fn collect() -> Self {
+        Self::from_signals(
+            |day| Path::new(&get_path_for_bin(day)).exists(),
+            &Timings::read_from_file(),
+            &Answers::read_from_file(),
+        )
+    }
+
+    pub fn from_signals(
+        is_scaffolded: impl Fn(Day) -> bool,
+        timings: &Timings,
+        answers: &Answers,
+    ) -> Self {
+        let part_status = |day: Day, part: u8| -> PartStatus {
+            if !day.has_part(part) {
+                return PartStatus::Unavailable;
+            }
+
+            let answer = answers.get(day, part);
+            let is_benched = timings
+                .data
+                .iter()
+                .any(|t| t.day == day && t.part(part).is_some());
+
+            match answer.and_then(|a| a.verdict) {
+                // the star was awarded, even if the stored answer is not the one that earned it.
+                Some(Verdict::Correct | Verdict::AlreadySolved) => PartStatus::Solved,
+                Some(Verdict::Incorrect) => PartStatus::Rejected,
+                None if is_benched => PartStatus::Solved,
+                None if answer.is_some() => PartStatus::Answered,
+                None if is_scaffolded(day) => PartStatus::Scaffolded,
+                None => PartStatus::Missing,
+            }
+        };
+
+        let mut days: Vec<DayProgress> = all_days()
+            .map(|day| DayProgress {
+                day,
+                part_1: part_status(day, 1),
+                part_2: part_status(day, 2),
+            })
+            .collect();
+
+        // the second star of day 25 is awarded for collecting all other stars.
+        if days.iter().all(DayProgress::is_complete) {
+            if let Some(last) = days.last_mut() {
+                last.part_2 = PartStatus::Solved;
+            }
+        }
+
+        Progress { days }
+    }
+
+    pub fn total_stars(&self) -> usize {
+        self.days.iter().map(DayProgress::stars).sum()
+    }
+
+    pub fn remaining_days(&self) -> usize {
+        self.days.iter().filter(|d| !d.is_complete()).count()
+    }
+}
+
+/// Render the progress as a calendar grid of five weeks.
+pub fn render_calendar(progress: &Progress) -> String {
+    let mut lines: Vec<String> = progress
+        .days
+        .chunks(5)
+        .map(|week| {
+            week.iter()
+                .map(|d| {
+                    let symbols = format!("{}{}", d.part_1.symbol(), d.part_2.symbol());
+                    if d.is_complete() {
+                        format!("{ANSI_BOLD}{}{ANSI_RESET} {symbols}", d.day)
+                    } else {
+                        format!("{} {symbols}", d.day)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("   ")
+        })
+        .collect();
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{ANSI_BOLD}Stars:{ANSI_RESET} {}/{} · {ANSI_BOLD}Remaining days:{ANSI_RESET} {}",
+        progress.total_stars(),
+        crate::template::MAX_STARS,
+        progress.remaining_days()
+    ));
+    lines.push("★ solved  ☆ answered  ✖ rejected  · scaffolded".into());
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartStatus, Progress};
+    use crate::{
+        day,
+        template::{
+            all_days,
+            answers::{Answers, Verdict},
+            timings::{Timing, Timings},
+            MAX_STARS,
+        },
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some("10ms".into()),
+                part_2: Some("20ms".into()),
+                part_1_error: None,
+                part_2_error: None,
+                total_nanos: 3e+7,
+                peak_bytes: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn combines_signals() {
+        let mut answers = Answers::default();
+        answers.record_verdict(day!(2), 1, "4", Verdict::Correct);
+        answers.record_verdict(day!(2), 2, "5", Verdict::Incorrect);
+        answers.record(day!(3), 1, "6");
+
+        let progress = Progress::from_signals(|day| day <= 4, &get_mock_timings(), &answers);
+
+        assert_eq!(progress.days[0].part_1, PartStatus::Solved);
+        assert_eq!(progress.days[0].part_2, PartStatus::Solved);
+        assert_eq!(progress.days[1].part_1, PartStatus::Solved);
+        assert_eq!(progress.days[1].part_2, PartStatus::Rejected);
+        assert_eq!(progress.days[2].part_1, PartStatus::Answered);
+        assert_eq!(progress.days[2].part_2, PartStatus::Scaffolded);
+        assert_eq!(progress.days[3].part_1, PartStatus::Scaffolded);
+        assert_eq!(progress.days[4].part_1, PartStatus::Missing);
+        assert_eq!(progress.days[24].part_2, PartStatus::Unavailable);
+        assert_eq!(progress.total_stars(), 3);
+        assert_eq!(progress.remaining_days(), 24);
+    }
+
+    #[test]
+    fn awards_day_25_bonus_star() {
+        let mut answers = Answers::default();
+        for day in all_days() {
+            for &part in day.parts() {
+                answers.record_verdict(day, part, "1", Verdict::Correct);
+            }
+        }
+
+        let progress = Progress::from_signals(|_| true, &Timings::default(), &answers);
+
+        assert_eq!(progress.days[24].part_2, PartStatus::Solved);
+        assert_eq!(progress.total_stars(), MAX_STARS);
+        assert_eq!(progress.remaining_days(), 0);
+    }
+}
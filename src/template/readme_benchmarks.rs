@@ -2,8 +2,9 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::heap_profile::format_bytes;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, MAX_STARS};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,8 +22,8 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
@@ -30,8 +31,9 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates a table delimited by two occurrences of `marker`.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,37 +54,66 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
+/// Formats a table cell for a part. Parts that do not exist for a day (e.g. day 25, part 2) are marked as such.
+fn format_part(timing: &Timing, part: u8) -> String {
+    if !timing.day.has_part(part) {
+        return "n/a".into();
+    }
+
+    match timing.part(part) {
+        Some(time) => format!("`{time}`"),
+        None => "`-`".into(),
+    }
+}
+
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let total_stars = timings.total_stars();
+    // the memory column is only shown once a day has been profiled with `cargo solve --dhat`.
+    let has_memory = timings.has_peak_bytes();
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Part 1 | Part 2 | Peak memory |".into());
+        lines.push("| :---: | :---: | :---:  | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+        let mut line = format!(
+            "| [Day {}]({}) | {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            format_part(&timing, 1),
+            format_part(&timing, 2)
+        );
+
+        if has_memory {
+            let memory = timing.peak_bytes.map_or_else(
+                || "`-`".into(),
+                |bytes| format!("`{}`", format_bytes(bytes)),
+            );
+            line.push_str(&format!(" {memory} |"));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(format!(
+        "**Total: {total_millis:.2}ms** · {total_stars}/{MAX_STARS} ⭐"
+    ));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
+    let positions = locate_table(s, MARKER)?;
     let table = construct_table("##", timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
@@ -109,19 +140,28 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 3e+10,
+                    peak_bytes: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 7e+10,
+                    peak_bytes: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 9e+10,
+                    peak_bytes: None,
                 },
             ],
         }
@@ -173,11 +213,41 @@ mod tests {
             "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
             "",
-            "**Total: 190.00ms**",
+            "**Total: 190.00ms** · 6/50 ⭐",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_column() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.set_peak_bytes(day!(2), 2048);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Peak memory |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `-` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `2.0KiB` |"));
+    }
+
+    #[test]
+    fn format_single_part_days() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(25),
+                part_1: Some("1ms".into()),
+                part_2: None,
+                part_1_error: None,
+                part_2_error: None,
+                total_nanos: 1e+6,
+                peak_bytes: None,
+            }],
+        };
+        update_content(&mut s, timings, 1.0).unwrap();
+        assert!(s.contains("| [Day 25](./src/bin/25.rs) | `1ms` | n/a |"));
+        assert!(s.contains("**Total: 1.00ms** · 1/50 ⭐"));
+    }
 }
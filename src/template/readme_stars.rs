@@ -0,0 +1,114 @@
+/// Module that updates the readme with star progress.
+/// The table format matches the one written by `aoc-readme-stars`, so both can be used interchangeably.
+use std::fs;
+
+use crate::template::progress::{PartStatus, Progress};
+use crate::template::readme_benchmarks::{locate_table, Error};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+fn format_part(status: PartStatus) -> &'static str {
+    if status == PartStatus::Solved {
+        "⭐"
+    } else {
+        " "
+    }
+}
+
+fn construct_table(prefix: &str, progress: &Progress, year: Option<u16>) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Results"),
+        None => format!("{prefix} Results"),
+    };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in progress.days.iter().filter(|d| d.stars() > 0) {
+        let label = match year {
+            Some(year) => format!(
+                "[Day {0}](https://adventofcode.com/{year}/day/{0})",
+                day.day.into_inner()
+            ),
+            None => format!("Day {}", day.day.into_inner()),
+        };
+
+        lines.push(format!(
+            "| {label} | {} | {} |",
+            format_part(day.part_1),
+            format_part(day.part_2)
+        ));
+    }
+
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &Progress, year: Option<u16>) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", progress, year);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(progress: &Progress, year: Option<u16>) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress, year)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::{
+        day,
+        template::{
+            answers::{Answers, Verdict},
+            progress::Progress,
+            timings::Timings,
+        },
+    };
+
+    fn get_mock_progress() -> Progress {
+        let mut answers = Answers::default();
+        answers.record_verdict(day!(1), 1, "1", Verdict::Correct);
+        answers.record_verdict(day!(1), 2, "2", Verdict::Correct);
+        answers.record_verdict(day!(2), 1, "3", Verdict::Correct);
+        Progress::from_signals(|_| true, &Timings::default(), &answers)
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\n{}\nbar", MARKER, MARKER);
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
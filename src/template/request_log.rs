@@ -0,0 +1,255 @@
+/// Keeps track of requests to adventofcode.com, so that downloads, submissions and leaderboard fetches are spaced out,
+/// even across invocations.
+use std::{
+    env, fs,
+    io::{self, stdout, Write},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+static REQUEST_LOG_PATH: &str = "./data/requests.log";
+
+/// Minimum spacing between two requests, unless configured with `AOC_REQUEST_INTERVAL`.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Sent with leaderboard fetches when `AOC_USER_AGENT` is not set. It names the template but nobody to contact, so
+/// users should set `AOC_USER_AGENT` to their own contact details. Downloads and submissions go through aoc-cli,
+/// which has no option for the User-Agent and always sends its own.
+const DEFAULT_USER_AGENT: &str = "advent-of-code-rust template";
+
+/// Only the most recent entries are kept.
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Entry {
+    /// A request of some kind, e.g. `download` or `submit`, at a unix timestamp.
+    Request { at: u64, kind: String },
+    /// The website asked to wait with submissions until the given unix timestamp.
+    Cooldown { at: u64, until: u64 },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RequestLog {
+    pub entries: Vec<Entry>,
+}
+
+impl RequestLog {
+    /// Read the log from disk. If not present or invalid, returns an empty log.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(REQUEST_LOG_PATH)
+            .map(|s| RequestLog::parse(&s))
+            .unwrap_or_default()
+    }
+
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        fs::write(REQUEST_LOG_PATH, self.serialize())
+    }
+
+    fn parse(s: &str) -> Self {
+        let entries = s
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                let at = fields.next()?.parse().ok()?;
+                match (fields.next()?, fields.next()?) {
+                    ("request", kind) => Some(Entry::Request {
+                        at,
+                        kind: kind.into(),
+                    }),
+                    ("cooldown", until) => Some(Entry::Cooldown {
+                        at,
+                        until: until.parse().ok()?,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        RequestLog { entries }
+    }
+
+    fn serialize(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| match entry {
+                Entry::Request { at, kind } => format!("{at}\trequest\t{kind}\n"),
+                Entry::Cooldown { at, until } => format!("{at}\tcooldown\t{until}\n"),
+            })
+            .collect()
+    }
+
+    pub fn push(&mut self, entry: Entry) {
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+    }
+
+    pub fn last_request(&self) -> Option<u64> {
+        self.entries.iter().rev().find_map(|entry| match entry {
+            Entry::Request { at, .. } => Some(*at),
+            Entry::Cooldown { .. } => None,
+        })
+    }
+
+    /// The time at which submitting is allowed again, if that is after `now`.
+    pub fn submit_allowed_at(&self, now: u64) -> Option<u64> {
+        self.entries
+            .iter()
+            .rev()
+            .find_map(|entry| match entry {
+                Entry::Cooldown { until, .. } => Some(*until),
+                Entry::Request { .. } => None,
+            })
+            .filter(|until| *until > now)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[must_use]
+pub fn get_interval() -> Duration {
+    env::var("AOC_REQUEST_INTERVAL")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .map_or(DEFAULT_INTERVAL, Duration::from_secs)
+}
+
+/// The User-Agent of the requests that the template sends itself, i.e. leaderboard fetches. Set `AOC_USER_AGENT` to
+/// something that identifies you, e.g. `github.com/<you>/advent-of-code by <email>`.
+#[must_use]
+pub fn get_user_agent() -> String {
+    match env::var("AOC_USER_AGENT") {
+        Ok(user_agent) if !user_agent.trim().is_empty() => user_agent,
+        _ => {
+            eprintln!("Warning: `AOC_USER_AGENT` is not set, set it to your contact details.");
+            DEFAULT_USER_AGENT.into()
+        }
+    }
+}
+
+/// Wait until the minimum spacing to the previous request has passed, then record a new request.
+pub fn throttle(kind: &str) {
+    let mut log = RequestLog::read_from_file();
+
+    if let Some(last) = log.last_request() {
+        let next = last + get_interval().as_secs();
+        let now = now();
+        if next > now {
+            print!(
+                "Waiting {} before the next request...",
+                format_wait(next - now)
+            );
+            let _ = stdout().flush();
+            thread::sleep(Duration::from_secs(next - now));
+            println!();
+        }
+    }
+
+    log.push(Entry::Request {
+        at: now(),
+        kind: kind.into(),
+    });
+
+    if let Err(e) = log.store_file() {
+        eprintln!("Failed to store request log: {e}");
+    }
+}
+
+/// Returns how long to wait if an earlier submission asked to wait before submitting again.
+pub fn check_submit_cooldown() -> Option<u64> {
+    let now = now();
+    RequestLog::read_from_file()
+        .submit_allowed_at(now)
+        .map(|until| until - now)
+}
+
+/// Remember that the website asked to wait `wait` before submitting again.
+pub fn record_submit_cooldown(wait: Duration) {
+    let mut log = RequestLog::read_from_file();
+    let at = now();
+    log.push(Entry::Cooldown {
+        at,
+        until: at + wait.as_secs(),
+    });
+
+    if let Err(e) = log.store_file() {
+        eprintln!("Failed to store request log: {e}");
+    }
+}
+
+/// Parses the wait time from a response like "You have 4m 32s left to wait."
+pub fn parse_wait(output: &str) -> Option<Duration> {
+    let (before, _) = output.split_once(" left to wait")?;
+    let (_, time) = before.rsplit_once("You have ")?;
+
+    let mut secs = 0;
+    for part in time.split_whitespace() {
+        let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        secs += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(secs))
+}
+
+#[must_use]
+pub fn format_wait(secs: u64) -> String {
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}s"),
+        (m, 0) => format!("{m}m"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_wait, parse_wait, Entry, RequestLog};
+
+    #[test]
+    fn parses_wait_times() {
+        let output = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait. [Return to Day 1]";
+        assert_eq!(parse_wait(output), Some(Duration::from_secs(272)));
+        assert_eq!(
+            parse_wait("You have 45s left to wait."),
+            Some(Duration::from_secs(45))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn formats_wait_times() {
+        assert_eq!(format_wait(272), "4m 32s");
+        assert_eq!(format_wait(60), "1m");
+        assert_eq!(format_wait(5), "5s");
+    }
+
+    #[test]
+    fn tracks_requests_and_cooldowns() {
+        let mut log = RequestLog::default();
+        log.push(Entry::Request {
+            at: 100,
+            kind: "submit".into(),
+        });
+        log.push(Entry::Cooldown {
+            at: 101,
+            until: 160,
+        });
+
+        let log = RequestLog::parse(&log.serialize());
+        assert_eq!(log.last_request(), Some(100));
+        assert_eq!(log.submit_allowed_at(120), Some(160));
+        assert_eq!(log.submit_allowed_at(160), None);
+    }
+}
@@ -1,13 +1,19 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{bench_config::BenchConfig, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    count_allocs: bool,
+    bench_config: &BenchConfig,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,10 +30,14 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output =
+                child_commands::run_solution(day, is_timed, is_release, count_allocs, bench_config)
+                    .unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
+            } else if child_commands::has_no_input(&output) {
+                // the solution printed the reason, there is nothing to time.
             } else {
                 let val = child_commands::parse_exec_time(&output, day);
                 timings.push(val);
@@ -68,7 +78,7 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{bench_config::BenchConfig, Day};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -77,23 +87,36 @@ pub mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        count_allocs: bool,
+        bench_config: &BenchConfig,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
         let day_padded = day.to_string();
+        let bench_args = bench_config.to_args();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
         if is_release {
             args.push("--release");
         }
 
+        if count_allocs {
+            args.push("--features");
+            args.push("count-allocs");
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
             args.push("--");
             args.push("--time");
+            args.extend(bench_args.iter().map(String::as_str));
         }
 
         // spawn child command with piped stdout/stderr.
@@ -128,14 +151,48 @@ pub mod child_commands {
         Ok(output)
     }
 
+    /// Whether the solution stopped because its input file was empty or a placeholder.
+    pub fn has_no_input(output: &[String]) -> bool {
+        output.iter().any(|line| line.starts_with("No input"))
+    }
+
+    /// Whether a part printed a result, timed or not.
+    pub fn is_part_solved(output: &[String], part: u8) -> bool {
+        let prefix = format!("Part {part}:");
+        output
+            .iter()
+            .any(|line| line.starts_with(&prefix) && !line.contains('✖'))
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            part_1_error: None,
+            part_2_error: None,
             total_nanos: 0_f64,
+            peak_bytes: None,
         };
 
+        // failed parts print their reason after the failure marker.
+        for line in output {
+            let Some((_, reason)) = line.rsplit_once('✖') else {
+                continue;
+            };
+
+            let reason = reason.trim();
+            if reason.is_empty() {
+                continue;
+            }
+
+            if line.starts_with("Part 1") {
+                timings.part_1_error = Some(reason.into());
+            } else if line.starts_with("Part 2") {
+                timings.part_2_error = Some(reason.into());
+            }
+        }
+
         output
             .iter()
             .filter_map(|l| {
@@ -174,7 +231,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +262,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{has_no_input, is_part_solved, parse_exec_time};
 
         use crate::day;
 
@@ -239,6 +296,20 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_with_allocation_counts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 1 (1ms @ 10 samples) [3 allocs, 1.2KiB]".into(),
+                    "Part 2: 2 (2ms @ 10 samples) [0 allocs, 0B]".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 3000000_f64);
+            assert_eq!(res.part_1.unwrap(), "1ms");
+            assert_eq!(res.part_2.unwrap(), "2ms");
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -253,5 +324,38 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_failure_reasons() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: ✖\rPart 1: ✖ bad line `x` (at src/bin/07.rs:31:9)".into(),
+                    "Part 2: 10 (100ms @ 1 samples)".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(
+                res.part_1_error.unwrap(),
+                "bad line `x` (at src/bin/07.rs:31:9)"
+            );
+            assert_eq!(res.part_2_error.is_none(), true);
+            assert_eq!(res.part_2.unwrap(), "100ms");
+        }
+
+        #[test]
+        fn detects_missing_input() {
+            assert!(has_no_input(&["No input: input file is empty.".into()]));
+            assert!(!has_no_input(&["Part 1: ✖ No input".into()]));
+        }
+
+        #[test]
+        fn detects_solved_parts() {
+            let output = [
+                "Part 1: \x1b[1m11\x1b[0m\rPart 1: \x1b[1m11\x1b[0m (35.8µs)".into(),
+                "Part 2: ✖\rPart 2: ✖ panicked (at src/bin/01.rs:1:1)".into(),
+            ];
+            assert!(is_part_solved(&output, 1));
+            assert!(!is_part_solved(&output, 2));
+        }
     }
 }
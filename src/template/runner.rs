@@ -1,91 +1,395 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
+use std::fs;
 use std::hint::black_box;
-use std::io::{stdout, Write};
+use std::io::{stdout, ErrorKind, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
+use crate::memo;
+use crate::template::alloc_counter::{self, AllocStats};
+use crate::template::answers::{Answers, Verdict};
+use crate::template::bench_config::{BenchConfig, SampleStats};
+use crate::template::download_meta::check_download;
+use crate::template::heap_profile::format_bytes;
+use crate::template::validate::{check_format, check_placeholder};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, profile, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Why a part did not produce an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The solution returned `None`.
+    NoResult,
+    /// The solution returned an error or panicked.
+    Error(String),
+}
+
+impl Failure {
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Failure::NoResult => None,
+            Failure::Error(reason) => Some(reason),
+        }
+    }
+}
+
+/// Return types that solution parts may use: `Option<T>` or `Result<T, E>`.
+pub trait PartResult {
+    type Answer: Display;
+
+    fn into_answer(self) -> Result<Self::Answer, Failure>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<T, Failure> {
+        self.ok_or(Failure::NoResult)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<T, Failure> {
+        self.map_err(|e| Failure::Error(e.to_string()))
+    }
+}
+
+/// Read the puzzle input of a day. Stops the run if the input file does not exist.
+pub fn read_input(day: Day) -> String {
+    match fs::read_to_string(profile::get_input_path(day)) {
+        Ok(input) => input,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            stop_without_input(day, "input file does not exist")
+        }
+        Err(e) => panic!("could not open input file: {e}"),
+    }
+}
+
+fn stop_without_input(day: Day, reason: &str) -> ! {
+    // NOTE: `run_multi` reads this line to mark the day as having no input.
+    println!("No input: {reason}.");
+    if env::args().any(|x| x == "--submit") {
+        eprintln!("Refusing to submit an answer for day {day} without a puzzle input.");
+    }
+    let profile_flag = profile::get_profile_flag();
+    eprintln!("Run `cargo download {day}{profile_flag}` to download the input for day {day}.");
+    process::exit(1);
+}
+
+/// Set once a solution reads one of its own arguments, see [`solution_arg`].
+static HAS_SOLUTION_ARGS: AtomicBool = AtomicBool::new(false);
+
+/// The value of a `--<name> <value>` argument for the solution itself, e.g. `cargo solve 11 -- --blinks 100`.
+/// Answers that depend on such an argument are not puzzle answers, so they are neither stored nor submitted.
+/// Stops the run if the value is missing or cannot be parsed.
+pub fn solution_arg<T: FromStr>(name: &str) -> Option<T>
+where
+    T::Err: Display,
+{
+    let flag = format!("--{name}");
+    let mut args = env::args().skip_while(|arg| *arg != flag);
+    args.next()?;
+
+    HAS_SOLUTION_ARGS.store(true, Ordering::Relaxed);
+
+    match args.next().map(|value| value.parse()) {
+        Some(Ok(value)) => Some(value),
+        Some(Err(e)) => {
+            eprintln!("Invalid value for `{flag}`: {e}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Missing value for `{flag}`.");
+            process::exit(1);
+        }
+    }
+}
+
+/// The default input check of the `solution!` macro for days without a `validate` hook.
+pub fn no_validation(_input: &str) -> Result<(), std::convert::Infallible> {
+    Ok(())
+}
+
+/// Check the puzzle input for structural problems before solving.
+/// Format problems are reported as warnings, while a missing input or an error returned by the day's `validate` hook
+/// stops the run. With `--validate`, the process exits after checking the input instead of solving.
+pub fn validate_input<E: Display>(input: &str, day: Day, validate: impl Fn(&str) -> Result<(), E>) {
+    let validate_only = env::args().any(|x| x == "--validate");
+
+    if let Some(reason) = check_placeholder(input) {
+        stop_without_input(day, reason);
+    }
+
+    let mut problems = check_format(input);
+    problems.extend(check_download(day, input));
+    for problem in &problems {
+        eprintln!("Warning: {problem}.");
+    }
+
+    let result = validate(input);
+
+    if let Err(e) = &result {
+        eprintln!("Invalid input for day {day}: {e}");
+        eprintln!(
+            "Check \"{}\" or run `cargo download {day}{}` to download it again.",
+            profile::get_input_path(day).display(),
+            profile::get_profile_flag()
+        );
+    }
+
+    if validate_only {
+        if result.is_ok() && problems.is_empty() {
+            println!("Input for day {day} is valid.");
+            process::exit(0);
+        }
+        process::exit(1);
+    }
+
+    if result.is_err() {
+        process::exit(1);
+    }
+}
+
+pub fn run_part<I: Clone, R: PartResult>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
+    run_part_with_setup(|| input.clone(), func, day, part);
+}
+
+/// Like [`run_part`], but prepares the input of every run with `setup`, e.g. to parse the puzzle input into an owned structure.
+/// The time spent in `setup` is not part of the measurement.
+pub fn run_part_with_setup<I, R: PartResult>(
+    setup: impl Fn() -> I,
+    func: impl Fn(I) -> R,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, measurement) = run_timed(setup, func, day, part, |result| {
+        print_result(result, &part_str, "")
+    });
+
+    let mut duration_str = format_duration(&measurement.duration, measurement.samples);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if cfg!(feature = "count-allocs") {
+        duration_str.push_str(&format_allocs(&measurement.allocs));
+    }
+
+    print_result(&result, &part_str, &duration_str);
+
+    if HAS_SOLUTION_ARGS.load(Ordering::Relaxed) {
+        let submit_part = env::args().skip_while(|x| x != "--submit").nth(1);
+        if submit_part == Some(part.to_string()) {
+            eprintln!("Refusing to submit an answer for part {part} that was computed with solution arguments.");
+        }
+        return;
+    }
+
+    if let Ok(result) = result {
+        let answer = result.to_string();
+        let verdict = submit_result(&answer, day, part)
+            .and_then(Result::ok)
+            .and_then(|output| Verdict::from_output(&String::from_utf8_lossy(&output.stdout)));
+        store_answer(day, part, &answer, verdict);
+    }
+}
+
+/// Keep track of the last answer for a part, as well as the verdict if it was submitted.
+fn store_answer(day: Day, part: u8, answer: &str, verdict: Option<Verdict>) {
+    let mut answers = Answers::read_from_file();
+
+    match verdict {
+        Some(verdict) => answers.record_verdict(day, part, answer, verdict),
+        None => answers.record(day, part, answer),
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Err(e) = answers.store_file() {
+        eprintln!("Failed to store answer: {e}");
     }
 }
 
+/// Measurements taken while running a solution part.
+struct Measurement {
+    duration: Duration,
+    samples: u128,
+    /// Allocations of the first run. Only counted if the `count-allocs` feature is enabled.
+    allocs: AllocStats,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+///     The budget, sample counts and warmup are configurable, see [`BenchConfig`].
+#[cfg_attr(not(feature = "dhat-heap"), allow(unused_variables))]
+fn run_timed<I, R: PartResult>(
+    setup: impl Fn() -> I,
+    func: impl Fn(I) -> R,
+    day: Day,
+    part: u8,
+    hook: impl Fn(&Result<R::Answer, Failure>),
+) -> (Result<R::Answer, Failure>, Measurement) {
+    // parts do not start with what an earlier part left in global caches.
+    memo::reset_global_caches();
+    let input = black_box(setup());
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+    // write one profile per part, so `solve --dhat` can summarize them separately.
+    #[cfg(feature = "dhat-heap")]
+    let profiler = dhat::Profiler::builder()
+        .file_name(crate::template::heap_profile::get_path_for_profile(
+            day, part,
+        ))
+        .build();
 
-        func(input)
-    };
+    let allocs_before = alloc_counter::snapshot();
+    let timer = Instant::now();
+    let result = catch_panic(|| func(input)).and_then(PartResult::into_answer);
     let base_time = timer.elapsed();
+    let allocs = alloc_counter::snapshot() - allocs_before;
+
+    #[cfg(feature = "dhat-heap")]
+    drop(profiler);
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    // failed parts are not benched, there is no timing to report for them.
+    let run = if result.is_ok() && std::env::args().any(|x| x == "--time") {
+        let config = BenchConfig::resolve().unwrap_or_else(|e| {
+            eprintln!("Invalid benchmark settings: {e}");
+            process::exit(1);
+        });
+        bench(setup, func, &base_time, &config)
     } else {
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (
+        result,
+        Measurement {
+            duration: run.0,
+            samples: run.1,
+            allocs,
+        },
+    )
+}
+
+/// The last panic, as recorded by the hook installed in [`catch_panic`].
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Run `f`, turning a panic into a [`Failure`] with the panic message and location.
+fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, Failure> {
+    if let Ok(mut last_panic) = LAST_PANIC.lock() {
+        *last_panic = None;
+    }
+
+    let default_hook = panic::take_hook();
+
+    // the hook also runs for panics of worker threads (e.g. rayon), which are then re-raised on this thread.
+    panic::set_hook(Box::new(|info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".into());
+
+        let reason = match info.location() {
+            Some(location) => format!("{message} (at {location})"),
+            None => message,
+        };
+
+        if let Ok(mut last_panic) = LAST_PANIC.lock() {
+            last_panic.get_or_insert(reason);
+        }
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(default_hook);
+
+    result.map_err(|_| {
+        let reason = LAST_PANIC.lock().ok().and_then(|mut p| p.take());
+        Failure::Error(reason.unwrap_or_else(|| "panicked".into()))
+    })
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Target duration of a batch of runs. Batching amortizes the overhead of reading the timer for fast solutions.
+const BATCH_TIME: Duration = Duration::from_micros(100);
+const MAX_BATCH_SIZE: u128 = 1000;
+
+/// Bench a solution in batches: inputs of a batch are prepared before and outputs are dropped after the timer runs,
+/// so neither is part of the measurement. When resetting global caches, every sample is its own batch.
+fn bench<I, T>(
+    setup: impl Fn() -> I,
+    func: impl Fn(I) -> T,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> (Duration, u128) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let reset_caches = config.reset_caches && memo::has_global_caches();
+
+    // setup is not measured, but counts towards the time budget and the batch size.
+    let mut setup_time = Duration::ZERO;
+    for _ in 0..config.warmup.max(1) {
+        if reset_caches {
+            memo::reset_global_caches();
+        }
+        let timer = Instant::now();
+        let input = black_box(setup());
+        setup_time = timer.elapsed();
+        if config.warmup > 0 {
+            black_box(func(input));
+        }
+    }
+
+    let batch_size = if reset_caches {
+        1
+    } else {
+        let run_time = *base_time + setup_time;
+        (BATCH_TIME.as_nanos() / run_time.as_nanos().max(1)).clamp(1, MAX_BATCH_SIZE)
+    };
+
+    let mut stats = SampleStats::default();
+    let mut elapsed = Duration::ZERO;
 
-    let mut timers: Vec<Duration> = vec![];
+    while !config.is_done(&stats, elapsed) {
+        let runs = batch_size.min(config.max_samples - stats.count);
+        let wall_timer = Instant::now();
+
+        if reset_caches {
+            memo::reset_global_caches();
+        }
+
+        let inputs: Vec<I> = (0..runs).map(|_| black_box(setup())).collect();
+        let mut outputs: Vec<T> = Vec::with_capacity(inputs.len());
 
-    for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
         let timer = Instant::now();
-        black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+        for input in inputs {
+            outputs.push(func(black_box(input)));
+        }
+        let batch_time = timer.elapsed();
+
+        black_box(outputs);
+        stats.push_batch(batch_time, runs);
+        elapsed += wall_timer.elapsed();
     }
 
     (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(stats.mean_nanos() as u64),
+        stats.count,
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
-
 fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
@@ -94,11 +398,19 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_allocs(allocs: &AllocStats) -> String {
+    format!(
+        " [{} allocs, {}]",
+        allocs.allocations,
+        format_bytes(allocs.bytes)
+    )
+}
+
+fn print_result<T: Display>(result: &Result<T, Failure>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -118,12 +430,16 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Err(failure) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                match failure.reason() {
+                    // NOTE: `run_multi` reads the reason from the text after the marker.
+                    Some(reason) => println!("{part}: ✖ {reason}"),
+                    None => println!("{part}: ✖             "),
+                }
             }
         }
     }
@@ -0,0 +1,81 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::panic::Location;
+
+/// An error returned by a solution, along with the location it was created at.
+///
+/// Any [`Error`] converts into a [`SolutionError`] with `?`, recording the location of the `?`.
+///
+/// ```
+/// # use advent_of_code::template::SolutionError;
+/// fn parse(s: &str) -> Result<u32, SolutionError> {
+///     Ok(s.parse::<u32>()?)
+/// }
+/// assert!(parse("x").unwrap_err().to_string().contains("invalid digit"));
+/// ```
+#[derive(Debug)]
+pub struct SolutionError {
+    message: String,
+    location: &'static Location<'static>,
+}
+
+impl SolutionError {
+    #[track_caller]
+    pub fn new(message: impl Display) -> Self {
+        Self {
+            message: message.to_string(),
+            location: Location::caller(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+// NOTE: `SolutionError` does not implement `Error` itself, as that would conflict with this impl.
+impl<E: Error> From<E> for SolutionError {
+    #[track_caller]
+    fn from(value: E) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at {})", self.message, self.location)
+    }
+}
+
+/// Shorthand for the return type of solutions that use [`SolutionError`].
+pub type SolutionResult<T> = Result<T, SolutionError>;
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{SolutionError, SolutionResult};
+
+    fn parse(s: &str) -> SolutionResult<u32> {
+        Ok(s.parse::<u32>()?)
+    }
+
+    #[test]
+    fn records_location_of_question_mark() {
+        let err = parse("x").unwrap_err();
+        assert_eq!(err.location().file(), file!());
+        assert_eq!(err.location().line(), line!() - 7);
+        assert!(err
+            .to_string()
+            .starts_with("invalid digit found in string (at "));
+    }
+
+    #[test]
+    fn records_location_of_new() {
+        let err = SolutionError::new("bad line");
+        assert_eq!(err.location().line(), line!() - 1);
+        assert_eq!(err.message(), "bad line");
+    }
+}
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{all_days, Day};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
@@ -11,7 +11,37 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Why a part failed to produce an answer, if the solution reported a reason.
+    pub part_1_error: Option<String>,
+    pub part_2_error: Option<String>,
     pub total_nanos: f64,
+    /// Peak heap usage over all parts in bytes, as measured by `cargo solve --dhat`.
+    pub peak_bytes: Option<u64>,
+}
+
+impl Timing {
+    /// Returns the benchmark string for a part, if it was benched.
+    pub fn part(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Number of stars collected for this day, one for every benched part that exists.
+    pub fn stars(&self) -> usize {
+        self.day
+            .parts()
+            .iter()
+            .filter(|&&part| self.part(part).is_some())
+            .count()
+    }
+
+    /// A day is complete once every part that exists for it has been benched.
+    pub fn is_complete(&self) -> bool {
+        self.stars() == self.day.parts().len()
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -38,11 +68,20 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Memory measurements are kept if `other` does not have one for a day.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if timing.peak_bytes.is_none() {
+                timing.peak_bytes = self
+                    .data
+                    .iter()
+                    .find(|t| t.day == timing.day)
+                    .and_then(|t| t.peak_bytes);
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,7 +90,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -60,10 +99,42 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Store the peak heap usage for a day, adding an empty timing if it has not been benched yet.
+    pub fn set_peak_bytes(&mut self, day: Day, peak_bytes: u64) {
+        match self.data.iter_mut().find(|t| t.day == day) {
+            Some(timing) => timing.peak_bytes = Some(peak_bytes),
+            None => {
+                self.data.push(Timing {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                    part_1_error: None,
+                    part_2_error: None,
+                    total_nanos: 0_f64,
+                    peak_bytes: Some(peak_bytes),
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+            }
+        }
+    }
+
+    pub fn has_peak_bytes(&self) -> bool {
+        self.data.iter().any(|t| t.peak_bytes.is_some())
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| t.day == day && t.is_complete())
+    }
+
+    /// Sum up collected stars. The second star of day 25 is awarded once all other days are complete.
+    pub fn total_stars(&self) -> usize {
+        let stars = self.data.iter().map(Timing::stars).sum::<usize>();
+
+        if all_days().all(|day| self.is_day_complete(day)) {
+            stars + 1
+        } else {
+            stars
+        }
     }
 }
 
@@ -114,6 +185,19 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        if let Some(error) = &value.part_1_error {
+            map.insert("part_1_error".into(), JsonValue::String(error.clone()));
+        }
+
+        if let Some(error) = &value.part_2_error {
+            map.insert("part_2_error".into(), JsonValue::String(error.clone()));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        if let Some(peak_bytes) = value.peak_bytes {
+            map.insert("peak_bytes".into(), JsonValue::Number(peak_bytes as f64));
+        }
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -166,11 +250,25 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // optional, timings stored before memory was tracked do not contain this key.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let peak_bytes = json
+            .get("peak_bytes")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64);
+
+        // optional, only present for failed parts.
+        let part_1_error = json.get("part_1_error").and_then(|v| v.get::<String>());
+        let part_2_error = json.get("part_2_error").and_then(|v| v.get::<String>());
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_error: part_1_error.cloned(),
+            part_2_error: part_2_error.cloned(),
             total_nanos,
+            peak_bytes,
         })
     }
 }
@@ -190,19 +288,28 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 3e+10,
+                    peak_bytes: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 7e+10,
+                    peak_bytes: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 4e+10,
+                    peak_bytes: None,
                 },
             ],
         }
@@ -221,6 +328,26 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.peak_bytes, None);
+        }
+
+        #[test]
+        fn handles_json_peak_bytes() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 0, "peak_bytes": 2048 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].peak_bytes, Some(2048));
+        }
+
+        #[test]
+        fn handles_json_errors() {
+            let json = r#"{ "data": [{ "day": "07", "part_1": null, "part_2": "1ms", "part_1_error": "bad line (at src/bin/07.rs:1:1)", "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1_error,
+                Some("bad line (at src/bin/07.rs:1:1)".to_string())
+            );
+            assert_eq!(timing.part_2_error, None);
         }
 
         #[test]
@@ -281,11 +408,14 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 3_000_000_000_f64,
+                    peak_bytes: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -295,11 +425,14 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 1_000_000_000_f64,
+                    peak_bytes: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,11 +442,69 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 0.0,
+                    peak_bytes: None,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+
+        #[test]
+        fn handles_single_part_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(25),
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    part_1_error: None,
+                    part_2_error: None,
+                    total_nanos: 1_000_000_000_f64,
+                    peak_bytes: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(25)), true);
+        }
+    }
+
+    mod total_stars {
+        use crate::{
+            day,
+            template::{
+                all_days,
+                timings::{Timing, Timings},
+                MAX_STARS,
+            },
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn counts_stars_per_part() {
+            assert_eq!(get_mock_timings().total_stars(), 5);
+        }
+
+        #[test]
+        fn awards_bonus_star_when_complete() {
+            let timings = Timings {
+                data: all_days()
+                    .map(|day| Timing {
+                        day,
+                        part_1: Some("1ms".into()),
+                        part_2: day.has_part(2).then(|| "1ms".into()),
+                        part_1_error: None,
+                        part_2_error: None,
+                        total_nanos: 0_f64,
+                        peak_bytes: None,
+                    })
+                    .collect(),
+            };
+
+            assert_eq!(timings.total_stars(), MAX_STARS);
+            assert_eq!(timings.data[24].day, day!(25));
         }
     }
 
@@ -333,7 +524,10 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 0_f64,
+                    peak_bytes: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -353,7 +547,10 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 0_f64,
+                    peak_bytes: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -365,6 +562,20 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_peak_bytes() {
+            let mut timings = get_mock_timings();
+            timings.set_peak_bytes(day!(2), 1024);
+            timings.set_peak_bytes(day!(3), 512);
+
+            let merged = timings.merge(&get_mock_timings());
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[1].peak_bytes, Some(1024));
+            assert_eq!(merged.data[2].day, day!(3));
+            assert_eq!(merged.data[2].peak_bytes, Some(512));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
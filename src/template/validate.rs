@@ -0,0 +1,90 @@
+/// Detect input files that do not contain a puzzle input, such as the empty files created by `scaffold`, or the error
+/// page written by a download with an expired session.
+pub fn check_placeholder(input: &str) -> Option<&'static str> {
+    if input.is_empty() {
+        return Some("input file is empty");
+    }
+
+    if input.trim().is_empty() {
+        return Some("input file only contains whitespace");
+    }
+
+    let lowercase = input.trim_start().to_lowercase();
+    let is_html = ["<!doctype", "<html", "<head", "<body"]
+        .iter()
+        .any(|tag| lowercase.starts_with(tag) || lowercase.contains(&format!("\n{tag}")));
+
+    if is_html || lowercase.contains("please log in to get your puzzle input") {
+        return Some("input file looks like a web page, the session may have expired");
+    }
+
+    None
+}
+
+/// Check the structure shared by all puzzle inputs. Downloaded inputs always end with exactly one newline.
+pub fn check_format(input: &str) -> Vec<String> {
+    let mut problems = vec![];
+
+    if input.is_empty() {
+        return problems;
+    }
+
+    let line_count = input.lines().count();
+
+    if input.contains('\r') {
+        problems.push("input has Windows line endings (`\\r\\n`)".into());
+    }
+
+    if !input.ends_with('\n') {
+        problems.push(format!(
+            "line {line_count}: input does not end with a newline, the download may be truncated"
+        ));
+    } else if input.ends_with("\n\n") || input.ends_with("\n\r\n") {
+        let content_lines = input.trim_end().lines().count();
+        problems.push(format!(
+            "line {}: input ends with {} blank line(s)",
+            content_lines + 1,
+            line_count - content_lines
+        ));
+    }
+
+    problems
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_format, check_placeholder};
+
+    #[test]
+    fn detects_placeholders() {
+        assert_eq!(check_placeholder(""), Some("input file is empty"));
+        assert!(check_placeholder(" \n\n").is_some());
+        assert!(check_placeholder("<!DOCTYPE html>\n<html lang=\"en-us\">").is_some());
+        assert!(check_placeholder(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        )
+        .is_some());
+
+        assert_eq!(check_placeholder("><<>>\n"), None);
+        assert_eq!(check_placeholder("3   4\n"), None);
+    }
+
+    #[test]
+    fn accepts_single_trailing_newline() {
+        assert!(check_format("").is_empty());
+        assert!(check_format("1\n2\n").is_empty());
+    }
+
+    #[test]
+    fn reports_trailing_newline_mismatch() {
+        assert_eq!(
+            check_format("1\n2"),
+            vec!["line 2: input does not end with a newline, the download may be truncated"]
+        );
+        assert_eq!(
+            check_format("1\n2\n\n\n"),
+            vec!["line 3: input ends with 2 blank line(s)"]
+        );
+        assert_eq!(check_format("1\r\n2\r\n").len(), 1);
+    }
+}